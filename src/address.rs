@@ -0,0 +1,599 @@
+//! `Address` is a module that parses `ed` style line addresses, like `5`, `$`, `.+3`, `1,$`,
+//! `/pattern/`, `?pattern?` and `'a`, and resolves them to a range of lines.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use regex::Regex;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The line an address starts from, before its offset is applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Base {
+    /// A (one based) line number, `0` is the position before the first line.
+    Line(usize),
+    /// The current line, `.`.
+    Current,
+    /// The last line, `$`.
+    Last,
+    /// The next line that matches a regular expression, searching forward (`/pattern/`) or
+    /// backward (`?pattern?`) from the current line and wrapping around. An empty pattern
+    /// repeats the last pattern of the context.
+    Search {
+        /// The regular expression.
+        pattern: String,
+        /// Searches forward when `true`.
+        forward: bool,
+    },
+    /// The line of a mark, `'a`.
+    Mark(char),
+}
+
+/************************************************************************************************/
+
+/// A single address, a base line with an offset like `.+3` or `$-1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address {
+    /// The base line.
+    pub base: Base,
+    /// The amount of lines added to the base line.
+    pub offset: isize,
+    /// The (one based) column of the address in the parsed text, used in errors.
+    pub column: usize,
+}
+
+/************************************************************************************************/
+
+/// A range of lines given by two addresses, like `1,$` or `/start/;/end/`. A single address is
+/// a range with two equal addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressRange {
+    /// The address of the first line.
+    pub first: Address,
+    /// The address of the last line.
+    pub last: Address,
+    /// The first line is the current line when resolving the last address when `true`, like the
+    /// `;` separator.
+    pub set_current: bool,
+}
+
+/************************************************************************************************/
+
+/// The state addresses are resolved against.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Context {
+    /// The index of the current line.
+    pub current: usize,
+    /// The indexes of the lines of the marks.
+    pub marks: HashMap<char, usize>,
+    /// The pattern repeated by an empty search, if any.
+    pub last_pattern: Option<String>,
+}
+
+/************************************************************************************************/
+
+/// The error returned when parsing or resolving an address fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressError {
+    /// The (one based) column in the address text.
+    pub column: usize,
+    /// The description of the problem.
+    pub message: String,
+}
+
+/************************************************************************************************/
+
+/// The `AddressLines` trait contains all functions to be implemented that resolve addresses
+/// against lines.
+pub trait AddressLines {
+    /// Parses an address and resolves it to the range of the indexes of the addressed lines. The
+    /// range of the address `0` is empty and marks the position before the first line.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - the address, like `2,$-1`.
+    /// * `context` - the current line, the marks and the last pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::address::*;
+    ///
+    /// let s = String::from("a\nb\nc\nd\ne");
+    /// let context = Context::new(1).mark('x', 3);
+    ///
+    /// assert_eq!(s.resolve_address("$", &context), Ok(4..5));
+    /// assert_eq!(s.resolve_address(".+1,'x", &context), Ok(2..4));
+    /// assert_eq!(s.resolve_address("/d/;+1", &context), Ok(3..5));
+    /// assert_eq!(s.resolve_address("?a?,.", &context), Ok(0..2));
+    /// assert_eq!(
+    ///     s.resolve_address("2,9", &context).unwrap_err().to_string(),
+    ///     "column 3: line 9 is out of range"
+    /// );
+    /// ```
+    fn resolve_address(
+        &self,
+        address: &str,
+        context: &Context,
+    ) -> Result<Range<usize>, AddressError>;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Address {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a new address without an offset.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - the base line.
+    pub fn new(base: Base) -> Self {
+        Address {
+            base,
+            offset: 0,
+            column: 1,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Resolves the address to a (one based) line number, `0` being the position before the
+    /// first line.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - the lines.
+    /// * `context` - the current line, the marks and the last pattern.
+    pub fn resolve(&self, lines: &[String], context: &Context) -> Result<usize, AddressError> {
+        let base = match &self.base {
+            Base::Line(line) => *line,
+            Base::Current => context.current + 1,
+            Base::Last => lines.len(),
+            Base::Mark(mark) => match context.marks.get(mark) {
+                Some(index) => index + 1,
+                None => return Err(self.error(&format!("mark '{}' is not set", mark))),
+            },
+            Base::Search { pattern, forward } => self.search(lines, context, pattern, *forward)?,
+        };
+        let line = isize::try_from(base)
+            .ok()
+            .and_then(|base| base.checked_add(self.offset))
+            .ok_or_else(|| self.error("line is out of range"))?;
+
+        if line < 0 || line as usize > lines.len() {
+            Err(self.error(&format!("line {} is out of range", line)))
+        } else {
+            Ok(line as usize)
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn search(
+        &self,
+        lines: &[String],
+        context: &Context,
+        pattern: &str,
+        forward: bool,
+    ) -> Result<usize, AddressError> {
+        let pattern = match (pattern, &context.last_pattern) {
+            ("", Some(last)) => last.as_str(),
+            ("", None) => return Err(self.error("no previous pattern")),
+            (pattern, _) => pattern,
+        };
+        let regex = Regex::new(pattern)
+            .map_err(|_| self.error(&format!("invalid pattern '{}'", pattern)))?;
+        let len = lines.len();
+
+        (1..=len)
+            .map(|step| {
+                if forward {
+                    (context.current + step) % len
+                } else {
+                    (context.current + len - step) % len
+                }
+            })
+            .find(|index| regex.is_match(&lines[*index]))
+            .map(|index| index + 1)
+            .ok_or_else(|| self.error(&format!("no match for '{}'", pattern)))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn error(&self, message: &str) -> AddressError {
+        AddressError::new(self.column, message)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl AddressRange {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Resolves the range to the range of the indexes of the addressed lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - the lines.
+    /// * `context` - the current line, the marks and the last pattern.
+    pub fn resolve(
+        &self,
+        lines: &[String],
+        context: &Context,
+    ) -> Result<Range<usize>, AddressError> {
+        let first = self.first.resolve(lines, context)?;
+        let last = if self.set_current {
+            let mut context = context.clone();
+            context.current = first.saturating_sub(1);
+            self.last.resolve(lines, &context)?
+        } else {
+            self.last.resolve(lines, context)?
+        };
+
+        if first > last {
+            return Err(self
+                .last
+                .error("the first address is after the last address"));
+        }
+
+        Ok(first.saturating_sub(1)..last)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl FromStr for AddressRange {
+    type Err = AddressError;
+
+    /// Parses a single address or a range of two addresses separated by `,` or `;`. A missing
+    /// first address is the first line for `,` and the current line for `;`. A missing last
+    /// address is the last line when the first address is missing too, otherwise it equals the
+    /// first address. `%` is the same as `,`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().collect(),
+            position: 0,
+        };
+        let range = parser.range()?;
+
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some(c) => Err(parser.error(&format!("unexpected '{}'", c))),
+            None => Ok(range),
+        }
+    }
+}
+
+/************************************************************************************************/
+
+impl Context {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a context without marks and without a last pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - the index of the current line.
+    pub fn new(current: usize) -> Self {
+        Context {
+            current,
+            marks: HashMap::new(),
+            last_pattern: None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets a mark.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - the name of the mark.
+    /// * `index` - the index of the marked line.
+    pub fn mark(mut self, mark: char, index: usize) -> Self {
+        self.marks.insert(mark, index);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the pattern repeated by an empty search.
+    pub fn last_pattern(mut self, pattern: &str) -> Self {
+        self.last_pattern = Some(String::from(pattern));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl AddressError {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(column: usize, message: &str) -> Self {
+        AddressError {
+            column,
+            message: String::from(message),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+/************************************************************************************************/
+
+impl std::error::Error for AddressError {}
+
+/************************************************************************************************/
+
+impl AddressLines for StringLines {
+    fn resolve_address(
+        &self,
+        address: &str,
+        context: &Context,
+    ) -> Result<Range<usize>, AddressError> {
+        address.parse::<AddressRange>()?.resolve(self, context)
+    }
+}
+
+/************************************************************************************************/
+
+impl AddressLines for String {
+    fn resolve_address(
+        &self,
+        address: &str,
+        context: &Context,
+    ) -> Result<Range<usize>, AddressError> {
+        self.split().resolve_address(address, context)
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The parser of address ranges, also used for the scripts of the `sed` module.
+pub(crate) struct Parser {
+    pub chars: Vec<char>,
+    pub position: usize,
+}
+
+/************************************************************************************************/
+
+impl Parser {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Parses an optional address range, this stops at the first character that is not part of
+    /// the range.
+    pub fn range(&mut self) -> Result<AddressRange, AddressError> {
+        self.skip_whitespace();
+
+        let column = self.position + 1;
+        let first = self.address()?;
+        self.skip_whitespace();
+
+        let separator = match self.peek() {
+            Some('%') if first.is_none() => Some(','),
+            Some(c) if c == ',' || c == ';' => Some(c),
+            _ => None,
+        };
+
+        let separator = match separator {
+            Some(separator) => {
+                self.position += 1;
+                separator
+            }
+            None => {
+                return match first {
+                    Some(first) => Ok(AddressRange {
+                        last: first.clone(),
+                        first,
+                        set_current: false,
+                    }),
+                    None => Err(AddressError::new(column, "missing address")),
+                }
+            }
+        };
+
+        let last = self.address()?;
+        let (first, last) = match (first, last) {
+            (Some(first), Some(last)) => (first, last),
+            (Some(first), None) => (first.clone(), first),
+            (None, last) => {
+                let first = Address {
+                    base: if separator == ',' {
+                        Base::Line(1)
+                    } else {
+                        Base::Current
+                    },
+                    offset: 0,
+                    column,
+                };
+                let last = last.unwrap_or(Address {
+                    base: Base::Last,
+                    offset: 0,
+                    column,
+                });
+                (first, last)
+            }
+        };
+
+        Ok(AddressRange {
+            first,
+            last,
+            set_current: separator == ';',
+        })
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Parses an optional single address.
+    pub fn address(&mut self) -> Result<Option<Address>, AddressError> {
+        self.skip_whitespace();
+
+        let column = self.position + 1;
+        let base = match self.peek() {
+            Some(c) if c.is_ascii_digit() => Some(Base::Line(self.number()?)),
+            Some('.') => {
+                self.position += 1;
+                Some(Base::Current)
+            }
+            Some('$') => {
+                self.position += 1;
+                Some(Base::Last)
+            }
+            Some(c) if c == '/' || c == '?' => {
+                self.position += 1;
+                Some(Base::Search {
+                    pattern: self.delimited(c),
+                    forward: c == '/',
+                })
+            }
+            Some('\'') => {
+                self.position += 1;
+                match self.peek() {
+                    Some(c) if c.is_ascii_lowercase() => {
+                        self.position += 1;
+                        Some(Base::Mark(c))
+                    }
+                    _ => return Err(self.error("expected the name of a mark")),
+                }
+            }
+            _ => None,
+        };
+
+        let mut offset: isize = 0;
+        let mut has_offset = false;
+        loop {
+            let start = self.position;
+            self.skip_whitespace();
+
+            let sign = match self.peek() {
+                Some('+') => 1,
+                Some('-') => -1,
+                _ => {
+                    self.position = start;
+                    break;
+                }
+            };
+            let sign_column = self.position + 1;
+            self.position += 1;
+
+            let amount = match self.peek() {
+                Some(c) if c.is_ascii_digit() => self.number()?,
+                _ => 1,
+            };
+            offset = isize::try_from(amount)
+                .ok()
+                .and_then(|amount| offset.checked_add(sign * amount))
+                .ok_or_else(|| AddressError::new(sign_column, "offset is too large"))?;
+            has_offset = true;
+        }
+
+        match base {
+            None if !has_offset => Ok(None),
+            base => Ok(Some(Address {
+                base: base.unwrap_or(Base::Current),
+                offset,
+                column,
+            })),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the text up to an unescaped delimiter, the delimiter is skipped. Escaped
+    /// delimiters are unescaped, other escapes are kept.
+    pub fn delimited(&mut self, delimiter: char) -> String {
+        let mut text = String::new();
+
+        while let Some(c) = self.peek() {
+            self.position += 1;
+            match c {
+                c if c == delimiter => break,
+                '\\' if self.peek() == Some(delimiter) => {
+                    text.push(delimiter);
+                    self.position += 1;
+                }
+                '\\' => {
+                    text.push('\\');
+                    if let Some(next) = self.peek() {
+                        text.push(next);
+                        self.position += 1;
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+
+        text
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn number(&mut self) -> Result<usize, AddressError> {
+        let start = self.position;
+
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+
+        self.chars[start..self.position]
+            .iter()
+            .collect::<String>()
+            .parse::<usize>()
+            .map_err(|_| AddressError::new(start + 1, "number is too large"))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    pub fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c == ' ' || c == '\t') {
+            self.position += 1;
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    pub fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    pub fn error(&self, message: &str) -> AddressError {
+        AddressError::new(self.position + 1, message)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,238 @@
+//! `Align` is a module that contains traits to align the columns of lines, like `column -t`, and
+//! to align a token like `=` or `:` over a range of lines.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::fields::*;
+use crate::lines::*;
+use crate::width::*;
+use std::ops::Range;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The way a cell is aligned within its column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    /// Aligned to the left, this is the default.
+    Left,
+    /// Aligned to the right.
+    Right,
+    /// Centred, an odd amount of padding puts the extra space on the right.
+    Center,
+}
+
+/************************************************************************************************/
+
+/// The options used when aligning columns.
+#[derive(Debug, Clone)]
+pub struct AlignOptions {
+    /// The separator between the columns of a line.
+    pub delimiter: Delimiter,
+    /// The separator between the aligned columns.
+    pub separator: String,
+    /// The alignment of each column, columns without an alignment are aligned to the left.
+    pub alignments: Vec<Alignment>,
+}
+
+/************************************************************************************************/
+
+/// The `AlignLines` trait contains all functions to be implemented that align lines.
+pub trait AlignLines {
+    /// Splits every line into columns and pads the columns to the display width of their widest
+    /// cell. The last column of a line is not padded on the right and empty lines are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for aligning.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::align::*;
+    ///
+    /// let s = String::from("name size\nfoo 12\nlonger 3");
+    /// let options = AlignOptions::new().alignments(vec![Alignment::Left, Alignment::Right]);
+    ///
+    /// assert_eq!(s.align_columns(&options), "name   size\nfoo      12\nlonger    3");
+    /// ```
+    fn align_columns(self, options: &AlignOptions) -> Self;
+
+    /// Aligns the first occurrence of a token in a range of lines by padding the text before
+    /// it. Trailing whitespace before the token is replaced by the padding, lines without the
+    /// token are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - the token to align, like `=`.
+    /// * `lines` - the range of the lines to align.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::align::*;
+    ///
+    /// let s = String::from("let a = 1;\nlet long = 2;\n\nlet b=3;");
+    ///
+    /// assert_eq!(s.align_on("=", 0..2), "let a    = 1;\nlet long = 2;\n\nlet b=3;");
+    /// ```
+    fn align_on(self, token: &str, lines: Range<usize>) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl AlignOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options, which split on whitespace and separate the columns with a
+    /// single space.
+    pub fn new() -> Self {
+        AlignOptions {
+            delimiter: Delimiter::Whitespace,
+            separator: String::from(" "),
+            alignments: Vec::new(),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the separator between the columns of a line.
+    pub fn delimiter(mut self, delimiter: Delimiter) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the separator between the aligned columns.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = String::from(separator);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the alignment of each column.
+    pub fn alignments(mut self, alignments: Vec<Alignment>) -> Self {
+        self.alignments = alignments;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the alignment of a (zero based) column.
+    pub fn alignment(&self, column: usize) -> Alignment {
+        self.alignments
+            .get(column)
+            .copied()
+            .unwrap_or(Alignment::Left)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for AlignOptions {
+    fn default() -> Self {
+        AlignOptions::new()
+    }
+}
+
+/************************************************************************************************/
+
+impl AlignLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn align_columns(self, options: &AlignOptions) -> Self {
+        let rows: Vec<Vec<&str>> = self
+            .iter()
+            .map(|line| {
+                if line.is_empty() {
+                    Vec::new()
+                } else {
+                    fields(line, &options.delimiter).collect()
+                }
+            })
+            .collect();
+        let mut widths: Vec<usize> = Vec::new();
+
+        for row in rows.iter() {
+            for (column, cell) in row.iter().enumerate() {
+                if column == widths.len() {
+                    widths.push(0);
+                }
+                widths[column] = widths[column].max(display_width(cell));
+            }
+        }
+
+        rows.iter()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .enumerate()
+                    .map(|(column, cell)| {
+                        let last = column + 1 == row.len();
+                        pad(cell, widths[column], options.alignment(column), !last)
+                    })
+                    .collect();
+                cells.join(&options.separator)
+            })
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn align_on(mut self, token: &str, lines: Range<usize>) -> Self {
+        let end = lines.end.min(self.len());
+        let start = lines.start.min(end);
+        let width = self[start..end]
+            .iter()
+            .filter_map(|line| {
+                line.find(token)
+                    .map(|index| display_width(line[..index].trim_end()))
+            })
+            .max();
+
+        if let (Some(width), false) = (width, token.is_empty()) {
+            for line in self[start..end].iter_mut() {
+                if let Some(index) = line.find(token) {
+                    let before = line[..index].trim_end();
+                    let padding = " ".repeat(width - display_width(before) + 1);
+                    *line = format!("{}{}{}", before, padding, &line[index..]);
+                }
+            }
+        }
+
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl AlignLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn align_columns(self, options: &AlignOptions) -> Self {
+        self.split().align_columns(options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn align_on(self, token: &str, lines: Range<usize>) -> Self {
+        self.split().align_on(token, lines).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,553 @@
+//! `Ansi` is a module that contains traits and functions to handle lines with ANSI escape
+//! sequences, like colour codes. Sequences are ignored when measuring and kept balanced when
+//! wrapping or truncating, so styles never leak into other lines.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use crate::width::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The sequence that resets all SGR styles.
+pub const RESET: &str = "\x1b[0m";
+
+/************************************************************************************************/
+
+/// The `AnsiLines` trait contains all functions to be implemented that handle lines with ANSI
+/// escape sequences.
+pub trait AnsiLines {
+    /// Removes all escape sequences from every line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::ansi::*;
+    ///
+    /// let s = String::from("\x1b[1;31merror\x1b[0m: x\n\x1b]8;;http://a.b\x07link\x1b]8;;\x07");
+    ///
+    /// assert_eq!(s.strip_ansi(), "error: x\nlink");
+    /// ```
+    fn strip_ansi(self) -> Self;
+
+    /// Returns the visible display width of the widest line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::ansi::*;
+    ///
+    /// assert_eq!(String::from("\x1b[32mok\x1b[0m\n日本").max_visible_width(), 4);
+    /// ```
+    fn max_visible_width(&self) -> usize;
+
+    /// Breaks every line that is wider than a visible display width into multiple lines. A line
+    /// that ends with active styles is reset, the next line reopens them.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the visible display width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::ansi::*;
+    ///
+    /// let s = String::from("a\x1b[31mbcd\x1b[0me");
+    ///
+    /// assert_eq!(s.wrap_ansi(2), "a\x1b[31mb\x1b[0m\n\x1b[31mcd\x1b[0m\ne");
+    /// ```
+    fn wrap_ansi(self, width: usize) -> Self;
+
+    /// Truncates every line that is wider than a visible display width, the ellipsis is
+    /// included in the width. All escape sequences are kept and a line that ends with active
+    /// styles is reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the visible display width.
+    /// * `options` - the options used for truncating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::ansi::*;
+    /// use rich_lib::width::*;
+    ///
+    /// let s = String::from("\x1b[1mbold text\x1b[0m!\nshort");
+    ///
+    /// assert_eq!(s.truncate_ansi(5, &WidthOptions::new()), "\x1b[1mbold…\x1b[0m\nshort");
+    /// ```
+    fn truncate_ansi(self, width: usize, options: &WidthOptions) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl AnsiLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn strip_ansi(self) -> Self {
+        self.iter().map(|line| strip_ansi(line)).collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn max_visible_width(&self) -> usize {
+        self.iter()
+            .map(|line| visible_width(line))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn wrap_ansi(self, width: usize) -> Self {
+        self.iter()
+            .flat_map(|line| wrap_ansi(line, width))
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn truncate_ansi(self, width: usize, options: &WidthOptions) -> Self {
+        self.iter()
+            .map(|line| truncate_ansi(line, width, options))
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl AnsiLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn strip_ansi(self) -> Self {
+        self.split().strip_ansi().merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn max_visible_width(&self) -> usize {
+        self.lines().map(visible_width).max().unwrap_or(0)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn wrap_ansi(self, width: usize) -> Self {
+        self.split().wrap_ansi(width).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn truncate_ansi(self, width: usize, options: &WidthOptions) -> Self {
+        self.split().truncate_ansi(width, options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Removes all escape sequences from a text: CSI sequences like SGR colour codes, OSC sequences
+/// like hyperlinks and two character escapes.
+///
+/// # Arguments
+///
+/// * `text` - the text to strip.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::ansi::*;
+///
+/// assert_eq!(strip_ansi("\x1b[38;5;208morange\x1b[m"), "orange");
+/// ```
+pub fn strip_ansi(text: &str) -> String {
+    pieces(text)
+        .iter()
+        .filter_map(|piece| match piece {
+            Piece::Text(text) => Some(*text),
+            Piece::Escape(_) => None,
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Returns the display width of a text, ignoring escape sequences.
+///
+/// # Arguments
+///
+/// * `text` - the text to measure.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::ansi::*;
+///
+/// let s = "\x1b[4mé\x1b[24m日";
+///
+/// assert_eq!(s.len(), 14);
+/// assert_eq!(visible_width(s), 3);
+/// ```
+pub fn visible_width(text: &str) -> usize {
+    display_width(&strip_ansi(text))
+}
+
+/************************************************************************************************/
+
+/// Breaks a text that is wider than a visible display width into multiple lines, on grapheme
+/// boundaries. A line that ends with active styles is reset, the next line reopens them.
+///
+/// # Arguments
+///
+/// * `text` - the text to wrap.
+/// * `width` - the visible display width, at least one grapheme is put on every line.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::ansi::*;
+///
+/// assert_eq!(
+///     wrap_ansi("\x1b[1m\x1b[32mabc\x1b[0m", 2),
+///     vec!["\x1b[1m\x1b[32mab\x1b[0m", "\x1b[1;32mc\x1b[0m"]
+/// );
+/// ```
+pub fn wrap_ansi(text: &str, width: usize) -> StringLines {
+    let mut lines = StringLines::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    let mut styles = Styles::new();
+
+    for piece in pieces(text) {
+        match piece {
+            Piece::Escape(escape) => {
+                styles.apply(escape);
+                line.push_str(escape);
+            }
+            Piece::Text(grapheme) => {
+                let grapheme_width = display_width(grapheme);
+                if line_width + grapheme_width > width && line_width > 0 {
+                    styles.close(&mut line);
+                    lines.push(std::mem::replace(&mut line, styles.open()));
+                    line_width = 0;
+                }
+                line.push_str(grapheme);
+                line_width += grapheme_width;
+            }
+        }
+    }
+
+    styles.close(&mut line);
+    lines.push(line);
+    lines
+}
+
+/************************************************************************************************/
+
+/// Truncates a text that is wider than a visible display width, the ellipsis is included in the
+/// width. All escape sequences are kept, so styles that start in the removed part still apply
+/// after it, and a text that ends with active styles is reset.
+///
+/// # Arguments
+///
+/// * `text` - the text to truncate.
+/// * `width` - the visible display width.
+/// * `options` - the options used for truncating.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::ansi::*;
+/// use rich_lib::width::*;
+///
+/// let options = WidthOptions::new().position(EllipsisPosition::Middle);
+///
+/// assert_eq!(
+///     truncate_ansi("ab\x1b[31mcdef\x1b[0mgh", 5, &options),
+///     "ab\x1b[31m…\x1b[0mgh"
+/// );
+/// ```
+pub fn truncate_ansi(text: &str, width: usize, options: &WidthOptions) -> String {
+    let pieces = pieces(text);
+    let visible: String = pieces
+        .iter()
+        .filter_map(|piece| match piece {
+            Piece::Text(text) => Some(*text),
+            Piece::Escape(_) => None,
+        })
+        .collect();
+
+    if text_width(&visible, options) <= width {
+        return String::from(text);
+    }
+
+    // the kept graphemes are found by truncating the visible text without an ellipsis, the
+    // ellipsis is put where the removed graphemes were
+    let ellipsis_width = text_width(&options.ellipsis, options);
+    let (ellipsis, available) = if ellipsis_width <= width {
+        (options.ellipsis.as_str(), width - ellipsis_width)
+    } else {
+        ("", width)
+    };
+    let plain = WidthOptions {
+        ellipsis: String::new(),
+        ..options.clone()
+    };
+    let kept = truncate_text(&visible, available, &plain);
+    let count = visible.graphemes(true).count();
+    let kept_graphemes: Vec<&str> = kept.graphemes(true).collect();
+    let head = match options.position {
+        EllipsisPosition::End => kept_graphemes.len(),
+        EllipsisPosition::Start => 0,
+        EllipsisPosition::Middle => {
+            let head_width = available - available / 2;
+            let mut used = 0;
+            kept_graphemes
+                .iter()
+                .take_while(|grapheme| {
+                    used += text_width(grapheme, options);
+                    used <= head_width
+                })
+                .count()
+        }
+    };
+    let tail_start = count - (kept_graphemes.len() - head);
+
+    let mut result = String::new();
+    let mut styles = Styles::new();
+    let mut index = 0;
+
+    for piece in pieces.iter() {
+        match piece {
+            Piece::Escape(escape) => {
+                styles.apply(escape);
+                result.push_str(escape);
+            }
+            Piece::Text(grapheme) => {
+                if index == head {
+                    result.push_str(ellipsis);
+                }
+                if index < head || index >= tail_start {
+                    result.push_str(grapheme);
+                }
+                index += 1;
+            }
+        }
+    }
+    if index == head {
+        result.push_str(ellipsis);
+    }
+
+    styles.close(&mut result);
+    result
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// A part of a text, an escape sequence or a single grapheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Piece<'a> {
+    Escape(&'a str),
+    Text(&'a str),
+}
+
+/************************************************************************************************/
+
+/// The SGR attributes that are active at some point of a text, as the code of the attribute
+/// and the parameters that set it.
+struct Styles {
+    active: Vec<(u16, String)>,
+}
+
+/************************************************************************************************/
+
+impl Styles {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new() -> Self {
+        Styles { active: Vec::new() }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Updates the active styles for an escape sequence. A reset clears all styles, a partial
+    /// reset like `22` or `39` only removes the attributes it ends.
+    fn apply(&mut self, escape: &str) {
+        let parameters: Vec<&str> = match escape
+            .strip_prefix("\x1b[")
+            .and_then(|sgr| sgr.strip_suffix('m'))
+        {
+            Some(parameters) => parameters.split(';').collect(),
+            None => return,
+        };
+        let mut index = 0;
+
+        while index < parameters.len() {
+            let parameter = parameters[index];
+            let code = match parameter.split(':').next().unwrap_or("") {
+                "" => 0,
+                code => code.parse().unwrap_or(u16::MAX),
+            };
+
+            let mut end = index + 1;
+            if matches!(code, 38 | 48 | 58) && !parameter.contains(':') {
+                end += match parameters.get(index + 1) {
+                    Some(&"5") => 2,
+                    Some(&"2") => 4,
+                    _ => 0,
+                };
+            }
+            let end = end.min(parameters.len());
+
+            if code == 0 {
+                self.active.clear();
+            } else if let Some(ended) = ended_attributes(code) {
+                self.active.retain(|(active, _)| !ended.contains(active));
+            } else {
+                let attribute = attribute(code);
+                self.active.retain(|(active, _)| *active != attribute);
+                self.active
+                    .push((attribute, parameters[index..end].join(";")));
+            }
+            index = end;
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the sequence that reopens the active styles.
+    fn open(&self) -> String {
+        if self.active.is_empty() {
+            String::new()
+        } else {
+            let parameters: Vec<&str> = self.active.iter().map(|(_, p)| p.as_str()).collect();
+            format!("\x1b[{}m", parameters.join(";"))
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Resets the active styles at the end of a line.
+    fn close(&self, line: &mut String) {
+        if !self.active.is_empty() {
+            line.push_str(RESET);
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+/// Returns the attribute set by an SGR code, all colours of the foreground, the background or the
+/// underline are the same attribute.
+fn attribute(code: u16) -> u16 {
+    match code {
+        30..=37 | 90..=97 => 38,
+        40..=47 | 100..=107 => 48,
+        _ => code,
+    }
+}
+
+/************************************************************************************************/
+
+/// Returns the attributes ended by an SGR code, `None` when the code does not end attributes.
+fn ended_attributes(code: u16) -> Option<&'static [u16]> {
+    match code {
+        22 => Some(&[1, 2]),
+        23 => Some(&[3]),
+        24 => Some(&[4, 21]),
+        25 => Some(&[5, 6]),
+        27 => Some(&[7]),
+        28 => Some(&[8]),
+        29 => Some(&[9]),
+        39 => Some(&[38]),
+        49 => Some(&[48]),
+        54 => Some(&[51, 52]),
+        55 => Some(&[53]),
+        59 => Some(&[58]),
+        _ => None,
+    }
+}
+
+/************************************************************************************************/
+
+/// Splits a text into escape sequences and graphemes. An unterminated sequence runs to the end
+/// of the text.
+fn pieces(text: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        match rest.find('\x1b') {
+            Some(0) => {
+                let length = escape_length(rest);
+                pieces.push(Piece::Escape(&rest[..length]));
+                rest = &rest[length..];
+            }
+            Some(start) => {
+                pieces.extend(rest[..start].graphemes(true).map(Piece::Text));
+                rest = &rest[start..];
+            }
+            None => {
+                pieces.extend(rest.graphemes(true).map(Piece::Text));
+                rest = "";
+            }
+        }
+    }
+
+    pieces
+}
+
+/************************************************************************************************/
+
+/// Returns the length in bytes of the escape sequence at the start of a text.
+fn escape_length(text: &str) -> usize {
+    let mut chars = text.char_indices().skip(1);
+
+    match chars.next() {
+        // CSI: parameters and intermediates followed by a final byte
+        Some((_, '[')) => chars
+            .find(|(_, c)| ('\x40'..='\x7e').contains(c))
+            .map_or(text.len(), |(index, c)| index + c.len_utf8()),
+        // OSC: terminated by BEL or ST (ESC \)
+        Some((_, ']')) => {
+            let mut previous = ' ';
+            for (index, c) in chars {
+                if c == '\x07' {
+                    return index + 1;
+                }
+                if previous == '\x1b' && c == '\\' {
+                    return index + 1;
+                }
+                previous = c;
+            }
+            text.len()
+        }
+        // nF: intermediates followed by a final byte, like ESC ( B
+        Some((_, c)) if ('\x20'..='\x2f').contains(&c) => chars
+            .find(|(_, c)| !('\x20'..='\x2f').contains(c))
+            .map_or(text.len(), |(index, c)| index + c.len_utf8()),
+        Some((index, c)) => index + c.len_utf8(),
+        None => text.len(),
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,267 @@
+//! `Blame` is a module that tracks which revision introduced each line of a text, given an ordered
+//! list of versions of that text.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::align::Alignment;
+use crate::diff::*;
+use crate::gutter::digits;
+use crate::lines::*;
+use crate::width::{display_width, pad_text};
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// A version of a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    /// The id of the revision, like a commit hash or an author.
+    pub id: String,
+    /// The text of this version.
+    pub text: String,
+}
+
+/************************************************************************************************/
+
+/// The origin of a line of the latest version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    /// The index of the revision that introduced the line.
+    pub revision: usize,
+    /// The index of the line in the revision that introduced it.
+    pub line: usize,
+    /// The text of the line in the latest version.
+    pub text: String,
+}
+
+/************************************************************************************************/
+
+/// The options used when tracking the origin of lines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlameOptions {
+    /// The options used to compare two versions. Lines that are equal because of these options
+    /// keep their origin.
+    pub diff: DiffOptions,
+    /// Moved lines keep their origin when set, moves are detected with the given options.
+    pub moves: Option<MoveOptions>,
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Revision {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a new revision.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the id of the revision.
+    /// * `text` - the text of this version.
+    pub fn new(id: &str, text: &str) -> Self {
+        Revision {
+            id: String::from(id),
+            text: String::from(text),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl BlameOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options, which compare lines exactly and do not detect moves.
+    pub fn new() -> Self {
+        BlameOptions {
+            diff: DiffOptions::new(),
+            moves: None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the options used to compare two versions.
+    pub fn diff(mut self, diff: DiffOptions) -> Self {
+        self.diff = diff;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether moved lines keep their origin, and the options used to detect them.
+    pub fn moves(mut self, moves: Option<MoveOptions>) -> Self {
+        self.moves = moves;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for BlameOptions {
+    fn default() -> Self {
+        BlameOptions::new()
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns for each line of the latest version the revision that introduced it. Every version
+/// is compared with the previous one, lines that are equal keep their origin and all other
+/// lines are attributed to the version they appear in.
+///
+/// # Arguments
+///
+/// * `revisions` - the versions of the text, from the oldest to the latest.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::blame::*;
+///
+/// let revisions = vec![
+///     Revision::new("r1", "a\nb"),
+///     Revision::new("r2", "a\nB\nc"),
+///     Revision::new("r3", "x\na\nB\nc"),
+/// ];
+/// let lines = blame(&revisions);
+///
+/// assert_eq!(lines[0], BlameLine { revision: 2, line: 0, text: String::from("x") });
+/// assert_eq!(lines[1], BlameLine { revision: 0, line: 0, text: String::from("a") });
+/// assert_eq!(lines[3], BlameLine { revision: 1, line: 2, text: String::from("c") });
+/// ```
+pub fn blame(revisions: &[Revision]) -> Vec<BlameLine> {
+    blame_with(revisions, &BlameOptions::new())
+}
+
+/************************************************************************************************/
+
+/// Returns for each line of the latest version the revision that introduced it, comparing the
+/// versions with the given options.
+///
+/// # Arguments
+///
+/// * `revisions` - the versions of the text, from the oldest to the latest.
+/// * `options` - the options used for comparing.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::blame::*;
+/// use rich_lib::diff::*;
+///
+/// let revisions = vec![
+///     Revision::new("r1", "a = 1\nb = 2"),
+///     Revision::new("r2", "a  =  1\nb = 3"),
+/// ];
+/// let options = BlameOptions::new().diff(DiffOptions::new().ignore_whitespace(true));
+/// let lines = blame_with(&revisions, &options);
+///
+/// assert_eq!((lines[0].revision, lines[1].revision), (0, 1));
+/// ```
+pub fn blame_with(revisions: &[Revision], options: &BlameOptions) -> Vec<BlameLine> {
+    let mut previous = match revisions.first() {
+        Some(first) => first.text.split(),
+        None => return Vec::new(),
+    };
+    let mut origins: Vec<(usize, usize)> = (0..previous.len()).map(|line| (0, line)).collect();
+
+    for (index, revision) in revisions.iter().enumerate().skip(1) {
+        let current = revision.text.split();
+        let mut d = previous.diff_with(&current, &options.diff);
+        if let Some(moves) = &options.moves {
+            d = d.detect_moves(moves);
+        }
+
+        let mut next: Vec<(usize, usize)> = (0..current.len()).map(|line| (index, line)).collect();
+        for op in d.ops.iter() {
+            if op.tag == Tag::Equal {
+                next[op.new.clone()].copy_from_slice(&origins[op.old.clone()]);
+            } else if op.is_moved_to() {
+                let from = d
+                    .ops
+                    .iter()
+                    .find(|from| from.is_moved_from() && from.moved == op.moved);
+                if let Some(from) = from {
+                    next[op.new.clone()].copy_from_slice(&origins[from.old.clone()]);
+                }
+            }
+        }
+
+        origins = next;
+        previous = current;
+    }
+
+    origins
+        .into_iter()
+        .zip(previous)
+        .map(|((revision, line), text)| BlameLine {
+            revision,
+            line,
+            text,
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Renders the lines of the latest version annotated with the id of the revision that
+/// introduced them and the (one based) line number in that revision.
+///
+/// # Arguments
+///
+/// * `revisions` - the versions of the text, from the oldest to the latest.
+/// * `lines` - the blamed lines of the latest version.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::blame::*;
+///
+/// let revisions = vec![Revision::new("r1", "a\nb"), Revision::new("r22", "a\nc\nb")];
+///
+/// assert_eq!(
+///     render_blame(&revisions, &blame(&revisions)),
+///     vec!["r1  1 | a", "r22 2 | c", "r1  2 | b"]
+/// );
+/// ```
+pub fn render_blame(revisions: &[Revision], lines: &[BlameLine]) -> StringLines {
+    let id = |line: &BlameLine| revisions.get(line.revision).map_or("", |r| r.id.as_str());
+    let id_width = lines
+        .iter()
+        .map(|line| display_width(id(line)))
+        .max()
+        .unwrap_or(0);
+    let number_width = lines
+        .iter()
+        .map(|line| digits(line.line + 1))
+        .max()
+        .unwrap_or(1);
+
+    lines
+        .iter()
+        .map(|line| {
+            format!(
+                "{} {:>number_width$} | {}",
+                pad_text(id(line), id_width, Alignment::Left),
+                line.line + 1,
+                line.text,
+                number_width = number_width
+            )
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,394 @@
+//! `Color` is a module that contains the styles and themes used to render coloured terminal
+//! output with ANSI escape sequences, and the choice whether to colour at all.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::ansi::RESET;
+use std::env;
+use std::ffi::OsStr;
+use std::io::{self, IsTerminal};
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// A terminal colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// Black.
+    Black,
+    /// Red.
+    Red,
+    /// Green.
+    Green,
+    /// Yellow.
+    Yellow,
+    /// Blue.
+    Blue,
+    /// Magenta.
+    Magenta,
+    /// Cyan.
+    Cyan,
+    /// White.
+    White,
+    /// One of the 256 colours of the extended palette.
+    Fixed(u8),
+    /// A 24-bit colour.
+    Rgb(u8, u8, u8),
+}
+
+/************************************************************************************************/
+
+/// The style of a piece of text. A style without colours and attributes leaves text unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    /// The colour of the text.
+    pub foreground: Option<Color>,
+    /// The colour behind the text.
+    pub background: Option<Color>,
+    /// Renders the text bold when `true`.
+    pub bold: bool,
+    /// Renders the text dimmed when `true`.
+    pub dim: bool,
+    /// Underlines the text when `true`.
+    pub underline: bool,
+    /// Swaps the foreground and background colour when `true`.
+    pub reverse: bool,
+}
+
+/************************************************************************************************/
+
+/// The styles used when rendering diffs and diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Deleted lines.
+    pub deleted: Style,
+    /// Inserted lines.
+    pub inserted: Style,
+    /// The deleted parts within a changed line.
+    pub deleted_highlight: Style,
+    /// The inserted parts within a changed line.
+    pub inserted_highlight: Style,
+    /// Moved lines.
+    pub moved: Style,
+    /// Equal lines shown as context.
+    pub context: Style,
+    /// The headers of the hunks of a unified diff.
+    pub hunk_header: Style,
+    /// Line numbers, separators and location arrows.
+    pub gutter: Style,
+    /// The severity of an error.
+    pub error: Style,
+    /// The severity of a warning.
+    pub warning: Style,
+    /// The severity of a note.
+    pub note: Style,
+    /// The severity of a help message.
+    pub help: Style,
+    /// The markers and messages of primary labels.
+    pub primary: Style,
+    /// The markers and messages of secondary labels.
+    pub secondary: Style,
+}
+
+/************************************************************************************************/
+
+/// The choice whether output is coloured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Colours when standard output is a terminal, the `NO_COLOR` environment variable is not
+    /// set and `TERM` is not `dumb`.
+    #[default]
+    Auto,
+    /// Always colours, also when `NO_COLOR` is set.
+    Always,
+    /// Never colours.
+    Never,
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Color {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the SGR parameters of the colour, for the foreground or the background.
+    fn parameters(self, background: bool) -> String {
+        let base = if background { 40 } else { 30 };
+
+        match self {
+            Color::Black => base.to_string(),
+            Color::Red => (base + 1).to_string(),
+            Color::Green => (base + 2).to_string(),
+            Color::Yellow => (base + 3).to_string(),
+            Color::Blue => (base + 4).to_string(),
+            Color::Magenta => (base + 5).to_string(),
+            Color::Cyan => (base + 6).to_string(),
+            Color::White => (base + 7).to_string(),
+            Color::Fixed(index) => format!("{};5;{}", base + 8, index),
+            Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Style {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a style that leaves text unchanged.
+    pub fn new() -> Self {
+        Style::default()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the colour of the text.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the colour behind the text.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the text bold.
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the text dimmed.
+    pub fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Underlines the text.
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Swaps the foreground and background colour.
+    pub fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when the style leaves text unchanged.
+    pub fn is_plain(&self) -> bool {
+        *self == Style::default()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the SGR sequence that starts the style, empty for a plain style.
+    pub fn prefix(&self) -> String {
+        let mut parameters = Vec::new();
+
+        if self.bold {
+            parameters.push(String::from("1"));
+        }
+        if self.dim {
+            parameters.push(String::from("2"));
+        }
+        if self.underline {
+            parameters.push(String::from("4"));
+        }
+        if self.reverse {
+            parameters.push(String::from("7"));
+        }
+        if let Some(color) = self.foreground {
+            parameters.push(color.parameters(false));
+        }
+        if let Some(color) = self.background {
+            parameters.push(color.parameters(true));
+        }
+
+        if parameters.is_empty() {
+            String::new()
+        } else {
+            format!("\x1b[{}m", parameters.join(";"))
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns a text in this style, followed by a reset. Empty texts and plain styles return
+    /// the text unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - the text to style.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::color::*;
+    ///
+    /// let style = Style::new().fg(Color::Red).bold();
+    ///
+    /// assert_eq!(style.paint("error"), "\x1b[1;31merror\x1b[0m");
+    /// assert_eq!(Style::new().paint("plain"), "plain");
+    /// ```
+    pub fn paint(&self, text: &str) -> String {
+        if self.is_plain() || text.is_empty() {
+            String::from(text)
+        } else {
+            format!("{}{}{}", self.prefix(), text, RESET)
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Theme {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default theme, red for deletions and errors, green for insertions and dimmed
+    /// context lines.
+    pub fn new() -> Self {
+        Theme {
+            deleted: Style::new().fg(Color::Red),
+            inserted: Style::new().fg(Color::Green),
+            deleted_highlight: Style::new().fg(Color::Red).reverse(),
+            inserted_highlight: Style::new().fg(Color::Green).reverse(),
+            moved: Style::new().fg(Color::Magenta),
+            context: Style::new().dim(),
+            hunk_header: Style::new().fg(Color::Cyan),
+            gutter: Style::new().fg(Color::Blue).bold(),
+            error: Style::new().fg(Color::Red).bold(),
+            warning: Style::new().fg(Color::Yellow).bold(),
+            note: Style::new().fg(Color::Green).bold(),
+            help: Style::new().fg(Color::Cyan).bold(),
+            primary: Style::new().fg(Color::Red).bold(),
+            secondary: Style::new().fg(Color::Blue).bold(),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a theme without any styles, rendering with it gives uncoloured output.
+    pub fn plain() -> Self {
+        Theme {
+            deleted: Style::new(),
+            inserted: Style::new(),
+            deleted_highlight: Style::new(),
+            inserted_highlight: Style::new(),
+            moved: Style::new(),
+            context: Style::new(),
+            hunk_header: Style::new(),
+            gutter: Style::new(),
+            error: Style::new(),
+            warning: Style::new(),
+            note: Style::new(),
+            help: Style::new(),
+            primary: Style::new(),
+            secondary: Style::new(),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the default theme when colours are enabled by the choice, the plain theme
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `choice` - the choice whether output is coloured.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::color::*;
+    ///
+    /// assert_eq!(Theme::for_choice(ColorChoice::Never), Theme::plain());
+    /// assert_eq!(Theme::for_choice(ColorChoice::Always), Theme::new());
+    /// ```
+    pub fn for_choice(choice: ColorChoice) -> Self {
+        if choice.enabled() {
+            Theme::new()
+        } else {
+            Theme::plain()
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when the theme has no styles.
+    pub fn is_plain(&self) -> bool {
+        *self == Theme::plain()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::new()
+    }
+}
+
+/************************************************************************************************/
+
+impl ColorChoice {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when output is coloured, for `Auto` this depends on the environment and
+    /// on whether standard output is a terminal.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => auto_enabled(
+                env::var_os("NO_COLOR").as_deref(),
+                env::var_os("TERM").as_deref(),
+                io::stdout().is_terminal(),
+            ),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns `true` when `Auto` colours, given the values of `NO_COLOR` and `TERM` and whether
+/// the output is a terminal. An empty `NO_COLOR` is ignored, see <https://no-color.org>.
+pub(crate) fn auto_enabled(no_color: Option<&OsStr>, term: Option<&OsStr>, terminal: bool) -> bool {
+    let no_color = no_color.is_some_and(|value| !value.is_empty());
+    let dumb = term.is_some_and(|term| term == "dumb");
+
+    terminal && !no_color && !dumb
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
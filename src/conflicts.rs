@@ -0,0 +1,393 @@
+//! `Conflicts` is a module that contains traits to find and resolve merge conflicts, marked with
+//! `<<<<<<<`, `|||||||`, `=======` and `>>>>>>>` lines.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use std::fmt;
+use std::ops::Range;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The minimum amount of characters of a conflict marker.
+pub const MIN_MARKER_SIZE: usize = 7;
+
+/************************************************************************************************/
+
+/// One side of a conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Side {
+    /// The label after the marker, like a branch name. Empty when there is none.
+    pub label: String,
+    /// The lines of this side.
+    pub lines: StringLines,
+}
+
+/************************************************************************************************/
+
+/// A conflict region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The range of the lines of the region, including the markers.
+    pub range: Range<usize>,
+    /// The amount of characters of the markers. Markers of other sizes within the region are
+    /// part of the sides, like the markers of a nested conflict.
+    pub marker_size: usize,
+    /// Our side, between the `<<<<<<<` and the `|||||||` or `=======` marker.
+    pub ours: Side,
+    /// The common ancestor, between the `|||||||` and `=======` marker of the diff3 style.
+    pub base: Option<Side>,
+    /// Their side, between the `=======` and `>>>>>>>` marker.
+    pub theirs: Side,
+}
+
+/************************************************************************************************/
+
+/// The way a conflict is resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keeps our side.
+    Ours,
+    /// Keeps their side.
+    Theirs,
+    /// Keeps the common ancestor.
+    Base,
+    /// Keeps our side followed by their side.
+    Both,
+    /// Replaces the region by the given lines.
+    Custom(StringLines),
+}
+
+/************************************************************************************************/
+
+/// The error returned when parsing or resolving conflicts fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictError {
+    /// The (one based) line of the problem.
+    pub line: usize,
+    /// The description of the problem.
+    pub message: String,
+}
+
+/************************************************************************************************/
+
+/// The `ConflictLines` trait contains all functions to be implemented that find and resolve merge
+/// conflicts.
+pub trait ConflictLines: Sized {
+    /// Returns all conflict regions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::conflicts::*;
+    ///
+    /// let s = String::from("a\n<<<<<<< ours\nb\n||||||| base\nc\n=======\nd\n>>>>>>> theirs");
+    /// let conflicts = s.conflicts().unwrap();
+    ///
+    /// assert_eq!(conflicts.len(), 1);
+    /// assert_eq!(conflicts[0].range, 1..8);
+    /// assert_eq!(conflicts[0].ours.label, "ours");
+    /// assert_eq!(conflicts[0].ours.lines, vec!["b"]);
+    /// assert_eq!(conflicts[0].base.as_ref().unwrap().lines, vec!["c"]);
+    /// assert_eq!(conflicts[0].theirs.lines, vec!["d"]);
+    /// ```
+    fn conflicts(&self) -> Result<Vec<Conflict>, ConflictError>;
+
+    /// Resolves all conflicts in the same way.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolution` - the way the conflicts are resolved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::conflicts::*;
+    ///
+    /// let s = String::from("a\n<<<<<<< ours\nb\n=======\nc\n>>>>>>> theirs\nd");
+    ///
+    /// assert_eq!(s.clone().resolve_conflicts(&Resolution::Theirs).unwrap(), "a\nc\nd");
+    /// assert_eq!(s.resolve_conflicts(&Resolution::Both).unwrap(), "a\nb\nc\nd");
+    /// ```
+    fn resolve_conflicts(self, resolution: &Resolution) -> Result<Self, ConflictError>;
+
+    /// Resolves all conflicts in the way returned by a function.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolve` - returns the resolution for the index of a conflict and the conflict.
+    fn resolve_conflicts_with<F>(self, resolve: F) -> Result<Self, ConflictError>
+    where
+        F: FnMut(usize, &Conflict) -> Resolution;
+
+    /// Resolves a single conflict, the other conflicts are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the index of the conflict.
+    /// * `resolution` - the way the conflict is resolved.
+    fn resolve_conflict(self, index: usize, resolution: &Resolution)
+        -> Result<Self, ConflictError>;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Side {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(label: &str) -> Self {
+        Side {
+            label: String::from(label),
+            lines: StringLines::new(),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Conflict {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the lines that replace the region for a given resolution.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolution` - the way the conflict is resolved.
+    pub fn resolve(&self, resolution: &Resolution) -> Result<StringLines, ConflictError> {
+        match resolution {
+            Resolution::Ours => Ok(self.ours.lines.clone()),
+            Resolution::Theirs => Ok(self.theirs.lines.clone()),
+            Resolution::Base => match &self.base {
+                Some(base) => Ok(base.lines.clone()),
+                None => Err(ConflictError::new(
+                    self.range.start + 1,
+                    "the conflict has no base",
+                )),
+            },
+            Resolution::Both => {
+                let mut lines = self.ours.lines.clone();
+                lines.extend(self.theirs.lines.iter().cloned());
+                Ok(lines)
+            }
+            Resolution::Custom(lines) => Ok(lines.clone()),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl ConflictError {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(line: usize, message: &str) -> Self {
+        ConflictError {
+            line,
+            message: String::from(message),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/************************************************************************************************/
+
+impl std::error::Error for ConflictError {}
+
+/************************************************************************************************/
+
+impl ConflictLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn conflicts(&self) -> Result<Vec<Conflict>, ConflictError> {
+        let mut conflicts = Vec::new();
+        let mut index = 0;
+
+        while index < self.len() {
+            match marker(&self[index], '<') {
+                Some((size, _)) => {
+                    let conflict = parse_conflict(self, index, size)?;
+                    index = conflict.range.end;
+                    conflicts.push(conflict);
+                }
+                None => index += 1,
+            }
+        }
+
+        Ok(conflicts)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn resolve_conflicts(self, resolution: &Resolution) -> Result<Self, ConflictError> {
+        self.resolve_conflicts_with(|_, _| resolution.clone())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn resolve_conflicts_with<F>(mut self, mut resolve: F) -> Result<Self, ConflictError>
+    where
+        F: FnMut(usize, &Conflict) -> Resolution,
+    {
+        let conflicts = self.conflicts()?;
+        let mut resolved = Vec::with_capacity(conflicts.len());
+
+        for (index, conflict) in conflicts.iter().enumerate() {
+            resolved.push(conflict.resolve(&resolve(index, conflict))?);
+        }
+        for (conflict, lines) in conflicts.iter().zip(resolved).rev() {
+            self.splice(conflict.range.clone(), lines);
+        }
+
+        Ok(self)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn resolve_conflict(
+        mut self,
+        index: usize,
+        resolution: &Resolution,
+    ) -> Result<Self, ConflictError> {
+        let conflicts = self.conflicts()?;
+        let conflict = conflicts.get(index).ok_or_else(|| {
+            ConflictError::new(self.len(), &format!("there is no conflict {}", index))
+        })?;
+
+        let lines = conflict.resolve(resolution)?;
+        self.splice(conflict.range.clone(), lines);
+        Ok(self)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl ConflictLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn conflicts(&self) -> Result<Vec<Conflict>, ConflictError> {
+        self.split().conflicts()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn resolve_conflicts(self, resolution: &Resolution) -> Result<Self, ConflictError> {
+        Ok(self.split().resolve_conflicts(resolution)?.merge())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn resolve_conflicts_with<F>(self, resolve: F) -> Result<Self, ConflictError>
+    where
+        F: FnMut(usize, &Conflict) -> Resolution,
+    {
+        Ok(self.split().resolve_conflicts_with(resolve)?.merge())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn resolve_conflict(
+        self,
+        index: usize,
+        resolution: &Resolution,
+    ) -> Result<Self, ConflictError> {
+        Ok(self.split().resolve_conflict(index, resolution)?.merge())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns the size and the label of a marker made of a given character, or `None` when the line
+/// is not such a marker. The marker is followed by the end of the line or a space and a label.
+fn marker(line: &str, c: char) -> Option<(usize, &str)> {
+    let size = line.chars().take_while(|x| *x == c).count();
+    let rest = &line[size..];
+
+    if size < MIN_MARKER_SIZE {
+        return None;
+    }
+
+    match rest.strip_prefix(' ') {
+        _ if rest.is_empty() => Some((size, "")),
+        Some(label) if c != '=' || label.trim().is_empty() => Some((size, label.trim_end())),
+        _ => None,
+    }
+}
+
+/************************************************************************************************/
+
+/// Returns the label of a marker made of a given character and of a given size.
+fn sized_marker(line: &str, c: char, size: usize) -> Option<(usize, &str)> {
+    marker(line, c).filter(|(s, _)| *s == size)
+}
+
+/************************************************************************************************/
+
+/// Parses the conflict that starts at a given line, only markers of the given size are taken
+/// into account.
+fn parse_conflict(lines: &[String], start: usize, size: usize) -> Result<Conflict, ConflictError> {
+    let label = marker(&lines[start], '<').map_or("", |(_, label)| label);
+    let mut sides = vec![Side::new(label)];
+    let mut has_base = false;
+
+    for (index, line) in lines.iter().enumerate().skip(start + 1) {
+        let in_theirs = sides.len() == if has_base { 3 } else { 2 };
+
+        if let Some((_, label)) = sized_marker(line, '|', size).filter(|_| sides.len() == 1) {
+            sides.push(Side::new(label));
+            has_base = true;
+        } else if sized_marker(line, '=', size).is_some() && !in_theirs {
+            sides.push(Side::new(""));
+        } else if let Some((_, label)) = sized_marker(line, '>', size) {
+            if !in_theirs {
+                return Err(ConflictError::new(index + 1, "missing '=' marker"));
+            }
+
+            let mut theirs = sides.pop().unwrap_or_else(|| Side::new(""));
+            theirs.label = String::from(label);
+            let base = if has_base { sides.pop() } else { None };
+            let ours = sides.pop().unwrap_or_else(|| Side::new(""));
+
+            return Ok(Conflict {
+                range: start..index + 1,
+                marker_size: size,
+                ours,
+                base,
+                theirs,
+            });
+        } else if sized_marker(line, '<', size).is_some() {
+            return Err(ConflictError::new(index + 1, "unexpected '<' marker"));
+        } else if let Some(side) = sides.last_mut() {
+            side.lines.push(line.clone());
+        }
+    }
+
+    Err(ConflictError::new(start + 1, "unterminated conflict"))
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
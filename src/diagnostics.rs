@@ -0,0 +1,654 @@
+//! `Diagnostics` is a module that renders messages about a source text, together with the
+//! offending source lines and markers underneath the labelled parts.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::color::{Style, Theme};
+use crate::gutter::{digits, render_gutter};
+use crate::lines::*;
+use crate::tabs::*;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::Range;
+use unicode_width::UnicodeWidthChar;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The severity of a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// An error.
+    Error,
+    /// A warning.
+    Warning,
+    /// A note.
+    Note,
+    /// A help message.
+    Help,
+}
+
+/************************************************************************************************/
+
+/// The style of a label, which determines the marker used to underline it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelStyle {
+    /// The main cause of a diagnostic, underlined with `^`.
+    Primary,
+    /// Additional context, underlined with `-`.
+    Secondary,
+}
+
+/************************************************************************************************/
+
+/// A labelled part of the source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    /// The style of the label.
+    pub style: LabelStyle,
+    /// The byte range of the labelled part in the source text.
+    pub range: Range<usize>,
+    /// The message shown next to the marker, may be empty.
+    pub message: String,
+}
+
+/************************************************************************************************/
+
+/// A message about a source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The severity.
+    pub severity: Severity,
+    /// The message.
+    pub message: String,
+    /// The labelled parts of the source text.
+    pub labels: Vec<Label>,
+    /// The name of the file shown in the header, if any.
+    pub file_name: Option<String>,
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/************************************************************************************************/
+
+impl Label {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a primary label.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - the byte range of the labelled part in the source text.
+    /// * `message` - the message shown next to the marker.
+    pub fn primary(range: Range<usize>, message: &str) -> Self {
+        Label {
+            style: LabelStyle::Primary,
+            range,
+            message: String::from(message),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a secondary label.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - the byte range of the labelled part in the source text.
+    /// * `message` - the message shown next to the marker.
+    pub fn secondary(range: Range<usize>, message: &str) -> Self {
+        Label {
+            style: LabelStyle::Secondary,
+            range,
+            message: String::from(message),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Diagnostic {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a diagnostic without labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - the severity.
+    /// * `message` - the message.
+    pub fn new(severity: Severity, message: &str) -> Self {
+        Diagnostic {
+            severity,
+            message: String::from(message),
+            labels: Vec::new(),
+            file_name: None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Adds a label.
+    pub fn label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the name of the file shown in the header.
+    pub fn file_name(mut self, file_name: &str) -> Self {
+        self.file_name = Some(String::from(file_name));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the diagnostic for a source text. The header contains the severity and the
+    /// message, followed by the location of the first primary label when a file name is set.
+    /// Below that the labelled source lines are shown with line numbers in a gutter. Labels
+    /// within a single line are underlined, labels spanning multiple lines are bracketed in the
+    /// margin. Tabs are expanded to four columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - the source text the labels refer to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diagnostics::*;
+    /// use rich_lib::lines::*;
+    ///
+    /// let source = "[server]\nport = \"80\"";
+    /// let d = Diagnostic::new(Severity::Error, "invalid type")
+    ///     .file_name("config.toml")
+    ///     .label(Label::primary(16..20, "expected a number"));
+    ///
+    /// let expected = vec![
+    ///     "error: invalid type",
+    ///     " --> config.toml:2:8",
+    ///     "  |",
+    ///     "2 | port = \"80\"",
+    ///     "  |        ^^^^ expected a number",
+    /// ];
+    ///
+    /// assert_eq!(d.render(source).split(), expected);
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        self.render_colored(source, &Theme::plain())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the diagnostic for a source text like `render`, coloured with a theme. The
+    /// severity is rendered in its style, the gutter and location arrow in the gutter style and
+    /// the marker rows in the style of the primary or secondary labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - the source text the labels refer to.
+    /// * `theme` - the styles of the parts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::color::*;
+    /// use rich_lib::diagnostics::*;
+    ///
+    /// let d = Diagnostic::new(Severity::Warning, "unused value");
+    ///
+    /// assert_eq!(
+    ///     d.render_colored("", &Theme::new()),
+    ///     "\x1b[1;33mwarning\x1b[0m: unused value"
+    /// );
+    /// ```
+    pub fn render_colored(&self, source: &str, theme: &Theme) -> String {
+        let severity = match self.severity {
+            Severity::Error => &theme.error,
+            Severity::Warning => &theme.warning,
+            Severity::Note => &theme.note,
+            Severity::Help => &theme.help,
+        };
+        let mut result = vec![format!(
+            "{}: {}",
+            severity.paint(&self.severity.to_string()),
+            self.message
+        )];
+
+        if self.labels.is_empty() {
+            return result.merge();
+        }
+
+        let source = Source::new(source);
+        let spans: Vec<Span> = self
+            .labels
+            .iter()
+            .map(|label| Span::new(label, &source))
+            .collect();
+        let shown: BTreeSet<usize> = spans
+            .iter()
+            .flat_map(|span| span.start.line..=span.end.line)
+            .collect();
+        let width = shown.iter().last().map_or(1, |line| digits(line + 1));
+
+        if let Some(file_name) = &self.file_name {
+            let span = spans
+                .iter()
+                .find(|span| span.style == LabelStyle::Primary)
+                .unwrap_or(&spans[0]);
+            result.push(format!(
+                "{} {}:{}:{}",
+                theme
+                    .gutter
+                    .paint(&format!("{:width$}-->", "", width = width)),
+                file_name,
+                span.start.line + 1,
+                span.start.char_column + 1
+            ));
+        }
+
+        result.push(themed_gutter(None, width, "", &theme.gutter, &Style::new()));
+
+        let multi: Vec<&Span> = spans.iter().filter(|span| span.is_multi_line()).collect();
+        let margin = multi.len() * 2;
+        let mut open = vec![false; multi.len()];
+        let mut previous: Option<usize> = None;
+
+        for line in shown {
+            if previous.is_some_and(|previous| line > previous + 1) {
+                result.push(theme.gutter.paint("..."));
+            }
+            previous = Some(line);
+
+            let mut row = Row::new();
+            for (slot, span) in multi.iter().enumerate() {
+                if span.start.line == line && span.starts_at_indent(&source) {
+                    open[slot] = true;
+                    row.set(slot * 2, '/');
+                } else if open[slot] {
+                    row.set(slot * 2, '|');
+                }
+            }
+            row.put(margin, &source.expanded(line));
+            result.push(themed_gutter(
+                Some(line + 1),
+                width,
+                &row.render(),
+                &theme.gutter,
+                &Style::new(),
+            ));
+
+            let mut rows = Vec::new();
+
+            let mut singles: Vec<&Span> = spans
+                .iter()
+                .filter(|span| !span.is_multi_line() && span.start.line == line)
+                .collect();
+            singles.sort_by_key(|span| span.start.column);
+            if !singles.is_empty() {
+                rows.append(&mut single_line_rows(&singles, margin, &open));
+            }
+
+            for (slot, span) in multi.iter().enumerate() {
+                if span.start.line == line && !open[slot] {
+                    let mut row = Row::with_bars(&open);
+                    row.fill(slot * 2 + 1, margin + span.start.column, '_');
+                    row.set(margin + span.start.column, span.marker());
+                    rows.push((row.render(), span.style));
+                    open[slot] = true;
+                }
+            }
+
+            for (slot, span) in multi.iter().enumerate() {
+                if span.end.line == line {
+                    let mut row = Row::with_bars(&open);
+                    row.fill(slot * 2 + 1, margin + span.end.column, '_');
+                    row.set(margin + span.end.column, span.marker());
+                    row.put(margin + span.end.column + 2, span.message);
+                    rows.push((row.render(), span.style));
+                    open[slot] = false;
+                }
+            }
+
+            for (row, style) in rows {
+                let style = match style {
+                    LabelStyle::Primary => &theme.primary,
+                    LabelStyle::Secondary => &theme.secondary,
+                };
+                result.push(themed_gutter(None, width, &row, &theme.gutter, style));
+            }
+        }
+
+        result.merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns the (zero based) line index and the (zero based) character column of a byte offset in
+/// a source text.
+///
+/// # Arguments
+///
+/// * `source` - the source text.
+/// * `offset` - the byte offset.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::diagnostics::*;
+///
+/// assert_eq!(line_column("line0\nliné1", 12), (1, 5));
+/// ```
+pub fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let position = Source::new(source).position(offset);
+    (position.line, position.char_column)
+}
+
+/************************************************************************************************/
+
+const TAB_WIDTH: usize = 4;
+
+/************************************************************************************************/
+
+/// The source text split into lines.
+struct Source<'a> {
+    text: &'a str,
+    starts: Vec<usize>,
+}
+
+/************************************************************************************************/
+
+/// A position in the source text.
+struct Position {
+    line: usize,
+    column: usize,
+    char_column: usize,
+}
+
+/************************************************************************************************/
+
+/// A label with its positions resolved.
+struct Span<'a> {
+    style: LabelStyle,
+    start: Position,
+    end: Position,
+    message: &'a str,
+}
+
+/************************************************************************************************/
+
+/// A single rendered row right of the gutter, every cell is a single display column.
+struct Row {
+    cells: Vec<String>,
+}
+
+/************************************************************************************************/
+
+impl<'a> Source<'a> {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(text: &'a str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(text.match_indices('\n').map(|(index, _)| index + 1));
+        Source { text, starts }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn line(&self, line: usize) -> &'a str {
+        let start = self.starts[line];
+        let end = self
+            .starts
+            .get(line + 1)
+            .map_or(self.text.len(), |end| end - 1);
+        self.text[start..end].trim_end_matches('\r')
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn expanded(&self, line: usize) -> String {
+        String::from(self.line(line)).expand_tabs(&TabOptions::new(TabStops::Uniform(TAB_WIDTH)))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line = self.starts.partition_point(|start| *start <= offset) - 1;
+        let text = self.line(line);
+        let index = (offset - self.starts[line]).min(text.len());
+
+        Position {
+            line,
+            column: display_column(text, index, &TabStops::Uniform(TAB_WIDTH)),
+            char_column: text[..index].chars().count(),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl<'a> Span<'a> {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(label: &'a Label, source: &Source) -> Self {
+        let start = source.position(label.range.start);
+        let mut end = source.position(label.range.end.max(label.range.start));
+
+        if end.line > start.line && end.column == 0 {
+            // a span ending at the start of a line actually ends at the end of the previous line
+            end = source.position(source.starts[end.line] - 1);
+        }
+        if end.line > start.line {
+            // the marker of a multi-line span points at its last character
+            end.column = end.column.saturating_sub(1);
+        }
+
+        Span {
+            style: label.style,
+            start,
+            end,
+            message: &label.message,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn is_multi_line(&self) -> bool {
+        self.start.line != self.end.line
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn starts_at_indent(&self, source: &Source) -> bool {
+        let text = source.expanded(self.start.line);
+        let indent = text.len() - text.trim_start().len();
+        self.start.column <= indent
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn marker(&self) -> char {
+        match self.style {
+            LabelStyle::Primary => '^',
+            LabelStyle::Secondary => '-',
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Row {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new() -> Self {
+        Row { cells: Vec::new() }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn with_bars(open: &[bool]) -> Self {
+        let mut row = Row::new();
+        for (slot, open) in open.iter().enumerate() {
+            if *open {
+                row.set(slot * 2, '|');
+            }
+        }
+        row
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn set(&mut self, column: usize, c: char) {
+        if self.cells.len() <= column {
+            self.cells.resize(column + 1, String::from(" "));
+        }
+        self.cells[column] = c.to_string();
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn fill(&mut self, from: usize, to: usize, c: char) {
+        for column in from..to {
+            self.set(column, c);
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn put(&mut self, column: usize, text: &str) {
+        let mut column = column;
+        for c in text.chars() {
+            match c.width().unwrap_or(0) {
+                0 => {
+                    if let Some(cell) = column
+                        .checked_sub(1)
+                        .and_then(|last| self.cells.get_mut(last))
+                    {
+                        cell.push(c);
+                    }
+                }
+                width => {
+                    self.set(column, c);
+                    for extra in 1..width {
+                        // the extra columns of a wide character are rendered by the character
+                        self.set(column + extra, ' ');
+                        self.cells[column + extra].clear();
+                    }
+                    column += width;
+                }
+            }
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn render(&self) -> String {
+        String::from(self.cells.concat().trim_end())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+/// Renders a single line with a gutter without trailing whitespace, the gutter and the line
+/// painted in their own style.
+fn themed_gutter(
+    number: Option<usize>,
+    width: usize,
+    line: &str,
+    gutter: &Style,
+    style: &Style,
+) -> String {
+    let rendered = render_gutter(number, width, " | ", line);
+    let rendered = rendered.trim_end();
+    let (head, tail) = rendered.split_at(rendered.len().min(width + 3));
+
+    format!("{}{}", gutter.paint(head), style.paint(tail))
+}
+
+/************************************************************************************************/
+
+fn single_line_rows(spans: &[&Span], margin: usize, open: &[bool]) -> Vec<(String, LabelStyle)> {
+    let mut rows = Vec::new();
+    let mut row = Row::with_bars(open);
+
+    for span in spans.iter() {
+        let width = (span.end.column.max(span.start.column + 1)) - span.start.column;
+        row.fill(
+            margin + span.start.column,
+            margin + span.start.column + width,
+            span.marker(),
+        );
+    }
+
+    let (last, others) = spans.split_last().expect("at least one span");
+    if !last.message.is_empty() {
+        let end = margin + last.end.column.max(last.start.column + 1);
+        row.put(end + 1, last.message);
+    }
+    let style = if spans.iter().any(|span| span.style == LabelStyle::Primary) {
+        LabelStyle::Primary
+    } else {
+        LabelStyle::Secondary
+    };
+    rows.push((row.render(), style));
+
+    let others: Vec<&&Span> = others
+        .iter()
+        .filter(|span| !span.message.is_empty())
+        .collect();
+    for index in (0..others.len()).rev() {
+        let mut connector = Row::with_bars(open);
+        for span in others[..=index].iter() {
+            connector.set(margin + span.start.column, '|');
+        }
+        rows.push((connector.render(), others[index].style));
+
+        let mut message = Row::with_bars(open);
+        for span in others[..index].iter() {
+            message.set(margin + span.start.column, '|');
+        }
+        message.put(margin + others[index].start.column, others[index].message);
+        rows.push((message.render(), others[index].style));
+    }
+
+    rows
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
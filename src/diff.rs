@@ -0,0 +1,640 @@
+//! `Diff` is a module that contains traits to compute and render the differences between lines.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+mod algorithm;
+mod inline;
+mod moves;
+mod patch;
+mod side_by_side;
+
+pub use self::algorithm::*;
+pub use self::inline::*;
+pub use self::moves::*;
+pub use self::patch::*;
+pub use self::side_by_side::*;
+
+use crate::color::Theme;
+use crate::lines::*;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The kind of an edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    /// The lines are the same in both texts.
+    Equal,
+    /// The lines are only in the old text.
+    Delete,
+    /// The lines are only in the new text.
+    Insert,
+}
+
+/************************************************************************************************/
+
+/// A single edit of an edit script. The ranges are the indexes of the lines in the old and new
+/// text, the range of the side that is not affected is empty and marks the position of the edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Op {
+    /// The kind of edit.
+    pub tag: Tag,
+    /// The range of lines in the old text.
+    pub old: Range<usize>,
+    /// The range of lines in the new text.
+    pub new: Range<usize>,
+    /// The id of the move when the deleted or inserted lines are moved. The block the lines are
+    /// moved from and the block they are moved to have the same id.
+    pub moved: Option<usize>,
+}
+
+/************************************************************************************************/
+
+/// The differences between an old and a new text as an edit script. Applying the operations in
+/// order to the old lines results in the new lines. In a change the deleted lines always
+/// precede the inserted lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineDiff {
+    /// The lines of the old text.
+    pub old: StringLines,
+    /// The lines of the new text.
+    pub new: StringLines,
+    /// The edit script.
+    pub ops: Vec<Op>,
+    /// Hides the deletes and inserts that consist only of blank lines when `true`. These stay in
+    /// the edit script, but they are not rendered and do not count as changes. In unified diffs
+    /// they are only shown when they are part of a hunk with other changes.
+    pub ignore_blank_lines: bool,
+}
+
+/************************************************************************************************/
+
+/// A line of the old text, the new text or both, in the order in which they are rendered. Paired
+/// changed lines have both indexes but are not equal. Moved lines are never paired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Row {
+    pub old: Option<usize>,
+    pub new: Option<usize>,
+    pub equal: bool,
+    pub moved: bool,
+}
+
+/************************************************************************************************/
+
+/// The `DiffLines` trait contains all functions to be implemented that compute the differences
+/// between lines.
+pub trait DiffLines {
+    /// Computes the differences between `self` (the old text) and another (new) text.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the new text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("line0\nline1\nline2");
+    /// let new = String::from("line0\nline2\nline3");
+    /// let d = old.diff(&new);
+    ///
+    /// assert_eq!(d.render(), vec![" line0", "-line1", " line2", "+line3"]);
+    /// ```
+    fn diff(&self, other: &Self) -> LineDiff;
+
+    /// Computes the differences between `self` (the old text) and another (new) text with the
+    /// given options.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the new text.
+    /// * `options` - the options used for comparing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("Line0\nline1");
+    /// let new = String::from("line0\n  line1");
+    /// let options = DiffOptions::new().ignore_case(true).ignore_whitespace(true);
+    ///
+    /// assert!(!old.diff_with(&new, &options).has_changes());
+    /// ```
+    fn diff_with(&self, other: &Self, options: &DiffOptions) -> LineDiff;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Op {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a new operation that is not part of a move.
+    pub fn new(tag: Tag, old: Range<usize>, new: Range<usize>) -> Self {
+        Op {
+            tag,
+            old,
+            new,
+            moved: None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a new operation that is part of the move with the given id.
+    pub fn moved(tag: Tag, old: Range<usize>, new: Range<usize>, id: usize) -> Self {
+        Op {
+            tag,
+            old,
+            new,
+            moved: Some(id),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when the lines of this operation are deleted because they are moved to
+    /// another position.
+    pub fn is_moved_from(&self) -> bool {
+        self.tag == Tag::Delete && self.moved.is_some()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when the lines of this operation are inserted because they are moved from
+    /// another position.
+    pub fn is_moved_to(&self) -> bool {
+        self.tag == Tag::Insert && self.moved.is_some()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl LineDiff {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Computes the differences between two sets of lines with the default options.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - the lines of the old text.
+    /// * `new` - the lines of the new text.
+    pub fn new(old: StringLines, new: StringLines) -> Self {
+        LineDiff::with_options(old, new, &DiffOptions::new())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when the edit script contains any deleted or inserted lines.
+    pub fn has_changes(&self) -> bool {
+        self.ops.iter().any(|op| self.is_change(op))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when an operation is a delete or an insert that consists only of blank
+    /// lines, while blank lines are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - an operation of the edit script.
+    pub fn is_ignored(&self, op: &Op) -> bool {
+        let lines = match op.tag {
+            Tag::Equal => return false,
+            Tag::Delete => &self.old[op.old.clone()],
+            Tag::Insert => &self.new[op.new.clone()],
+        };
+
+        self.ignore_blank_lines && lines.iter().all(|line| line.trim().is_empty())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders all lines, prefixed with `-` for deleted lines, `+` for inserted lines and a
+    /// space for equal lines. Moved lines are prefixed with `<` where they are moved from and
+    /// with `>` where they are moved to. Ignored blank line changes are left out.
+    pub fn render(&self) -> StringLines {
+        self.render_colored(&Theme::plain())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders all lines like `render`, coloured with a theme. Deleted and inserted lines are
+    /// coloured, the changed words within paired lines are highlighted and equal lines are
+    /// rendered in the context style.
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - the styles of the lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::color::*;
+    /// use rich_lib::diff::*;
+    ///
+    /// let diff = String::from("a\nb").diff(&String::from("a\nc"));
+    /// let theme = Theme::new();
+    ///
+    /// assert_eq!(diff.render_colored(&Theme::plain()), diff.render());
+    /// assert_eq!(diff.render_colored(&theme)[0], theme.context.paint(" a"));
+    /// ```
+    pub fn render_colored(&self, theme: &Theme) -> StringLines {
+        let ops: Vec<Op> = self.shown_ops().into_iter().cloned().collect();
+        self.render_ops(&ops, true, theme)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the differences in the unified format, grouped in hunks with a given amount of
+    /// context lines around the changes. Moved lines are rendered as deleted and inserted lines,
+    /// so the result stays a valid patch.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - the amount of equal lines shown before and after a change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("a\nb\nc\nd\ne\nf");
+    /// let new = String::from("a\nb\nc\nD\ne\nf");
+    ///
+    /// assert_eq!(
+    ///     old.diff(&new).render_unified(1),
+    ///     vec!["@@ -3,3 +3,3 @@", " c", "-d", "+D", " e"]
+    /// );
+    /// ```
+    pub fn render_unified(&self, context: usize) -> StringLines {
+        self.render_unified_colored(context, &Theme::plain())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the differences in the unified format like `render_unified`, coloured with a
+    /// theme. The hunk headers are rendered in the hunk header style.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - the amount of equal lines shown before and after a change.
+    /// * `theme` - the styles of the lines.
+    pub fn render_unified_colored(&self, context: usize, theme: &Theme) -> StringLines {
+        let mut result = StringLines::new();
+
+        for hunk in self.hunks(context) {
+            let first = &hunk[0];
+            let last = &hunk[hunk.len() - 1];
+
+            result.push(theme.hunk_header.paint(&format!(
+                "@@ -{} +{} @@",
+                hunk_range(&(first.old.start..last.old.end)),
+                hunk_range(&(first.new.start..last.new.end))
+            )));
+            result.append(&mut self.render_ops(&hunk, false, theme));
+        }
+
+        result
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Groups the operations in hunks with a given amount of context lines around the changes.
+    /// Equal operations are trimmed to the context lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - the amount of equal lines kept before and after a change.
+    pub fn hunks(&self, context: usize) -> Vec<Vec<Op>> {
+        let changes: Vec<usize> = (0..self.ops.len())
+            .filter(|index| self.is_change(&self.ops[*index]))
+            .collect();
+        let (first, last) = match (changes.first(), changes.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return Vec::new(),
+        };
+
+        let mut hunks = Vec::new();
+        let mut hunk = trailing_context(&self.ops[..first], context);
+
+        for pair in changes.windows(2) {
+            let gap = &self.ops[pair[0] + 1..pair[1]];
+            let equal: usize = gap
+                .iter()
+                .filter(|op| op.tag == Tag::Equal)
+                .map(|op| op.old.len())
+                .sum();
+
+            hunk.push(self.ops[pair[0]].clone());
+            if equal <= context.saturating_mul(2) {
+                hunk.extend(gap.iter().cloned());
+            } else {
+                hunk.append(&mut leading_context(gap, context));
+                hunks.push(std::mem::take(&mut hunk));
+                hunk = trailing_context(gap, context);
+            }
+        }
+
+        hunk.push(self.ops[last].clone());
+        hunk.append(&mut leading_context(&self.ops[last + 1..], context));
+        hunks.push(hunk);
+
+        hunks
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the rows of the diff, within a change the deleted and inserted lines are paired in
+    /// order.
+    pub(crate) fn rows(&self) -> Vec<Row> {
+        let ops = self.shown_ops();
+        let mut rows = Vec::new();
+        let mut index = 0;
+
+        while index < ops.len() {
+            let op = ops[index];
+            let (deleted, inserted) = match op.tag {
+                Tag::Equal => (op.old.clone(), op.new.clone()),
+                Tag::Insert => (op.old.start..op.old.start, op.new.clone()),
+                Tag::Delete if op.moved.is_some() => (op.old.clone(), op.new.start..op.new.start),
+                Tag::Delete => match ops.get(index + 1) {
+                    Some(next) if next.tag == Tag::Insert && next.moved.is_none() => {
+                        index += 1;
+                        (op.old.clone(), next.new.clone())
+                    }
+                    _ => (op.old.clone(), op.new.start..op.new.start),
+                },
+            };
+
+            if op.tag == Tag::Equal {
+                rows.extend(deleted.zip(inserted).map(|(old, new)| Row {
+                    old: Some(old),
+                    new: Some(new),
+                    equal: true,
+                    moved: false,
+                }));
+            } else {
+                let paired = deleted.len().min(inserted.len());
+                rows.extend(deleted.clone().zip(inserted.clone()).map(|(old, new)| Row {
+                    old: Some(old),
+                    new: Some(new),
+                    equal: false,
+                    moved: op.moved.is_some(),
+                }));
+                rows.extend(deleted.skip(paired).map(|old| Row {
+                    old: Some(old),
+                    new: None,
+                    equal: false,
+                    moved: op.moved.is_some(),
+                }));
+                rows.extend(inserted.skip(paired).map(|new| Row {
+                    old: None,
+                    new: Some(new),
+                    equal: false,
+                    moved: op.moved.is_some(),
+                }));
+            }
+
+            index += 1;
+        }
+
+        rows
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the operations that are rendered, all operations except the ignored ones.
+    pub(crate) fn shown_ops(&self) -> Vec<&Op> {
+        self.ops.iter().filter(|op| !self.is_ignored(op)).collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn is_change(&self, op: &Op) -> bool {
+        op.tag != Tag::Equal && !self.is_ignored(op)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn render_ops(&self, ops: &[Op], moves: bool, theme: &Theme) -> StringLines {
+        let mut result = StringLines::new();
+        let pairs: Vec<(usize, usize)> = if theme.is_plain() {
+            Vec::new()
+        } else {
+            self.paired_lines()
+        };
+        let old_pairs: HashMap<usize, usize> = pairs.iter().cloned().collect();
+        let new_pairs: HashMap<usize, usize> = pairs.iter().map(|&(old, new)| (new, old)).collect();
+
+        for op in ops.iter() {
+            let moved = moves && op.moved.is_some();
+            let (marker, style) = match op.tag {
+                Tag::Equal => (' ', &theme.context),
+                Tag::Delete if moved => ('<', &theme.moved),
+                Tag::Delete => ('-', &theme.deleted),
+                Tag::Insert if moved => ('>', &theme.moved),
+                Tag::Insert => ('+', &theme.inserted),
+            };
+            let indexes = if op.tag == Tag::Insert {
+                op.new.clone()
+            } else {
+                op.old.clone()
+            };
+
+            for index in indexes {
+                let tokens = match op.tag {
+                    Tag::Delete if !moved => old_pairs
+                        .get(&index)
+                        .map(|&new| diff_words(&self.old[index], &self.new[new])),
+                    Tag::Insert if !moved => new_pairs
+                        .get(&index)
+                        .map(|&old| diff_words(&self.old[old], &self.new[index])),
+                    _ => None,
+                };
+                let line = match tokens {
+                    Some(tokens) => format!(
+                        "{}{}",
+                        style.paint(&marker.to_string()),
+                        paint_tokens(&tokens, op.tag, theme)
+                    ),
+                    None if op.tag == Tag::Insert => {
+                        style.paint(&format!("{}{}", marker, self.new[index]))
+                    }
+                    None => style.paint(&format!("{}{}", marker, self.old[index])),
+                };
+                result.push(line);
+            }
+        }
+
+        result
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl DiffLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn diff(&self, other: &Self) -> LineDiff {
+        LineDiff::new(self.clone(), other.clone())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn diff_with(&self, other: &Self, options: &DiffOptions) -> LineDiff {
+        LineDiff::with_options(self.clone(), other.clone(), options)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl DiffLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn diff(&self, other: &Self) -> LineDiff {
+        LineDiff::new(self.split(), other.split())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn diff_with(&self, other: &Self, options: &DiffOptions) -> LineDiff {
+        LineDiff::with_options(self.split(), other.split(), options)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+fn hunk_range(range: &Range<usize>) -> String {
+    match range.len() {
+        0 => format!("{},0", range.start),
+        1 => format!("{}", range.start + 1),
+        len => format!("{},{}", range.start + 1, len),
+    }
+}
+
+/************************************************************************************************/
+
+/// Returns the operations of a gap between changes that are shown after a change, up to a given
+/// amount of equal lines. Ignored operations within these lines are kept whole.
+fn leading_context(ops: &[Op], context: usize) -> Vec<Op> {
+    let mut result = Vec::new();
+    let mut remaining = context;
+
+    for op in ops.iter() {
+        if remaining == 0 {
+            break;
+        }
+        if op.tag == Tag::Equal {
+            let len = op.old.len().min(remaining);
+            result.push(Op::new(
+                Tag::Equal,
+                op.old.start..op.old.start + len,
+                op.new.start..op.new.start + len,
+            ));
+            remaining -= len;
+        } else {
+            result.push(op.clone());
+        }
+    }
+
+    result
+}
+
+/************************************************************************************************/
+
+/// Returns the operations of a gap between changes that are shown before a change, up to a
+/// given amount of equal lines. Ignored operations within these lines are kept whole.
+fn trailing_context(ops: &[Op], context: usize) -> Vec<Op> {
+    let mut result = Vec::new();
+    let mut remaining = context;
+
+    for op in ops.iter().rev() {
+        if remaining == 0 {
+            break;
+        }
+        if op.tag == Tag::Equal {
+            let len = op.old.len().min(remaining);
+            result.push(Op::new(
+                Tag::Equal,
+                op.old.end - len..op.old.end,
+                op.new.end - len..op.new.end,
+            ));
+            remaining -= len;
+        } else {
+            result.push(op.clone());
+        }
+    }
+
+    result.reverse();
+    result
+}
+
+/************************************************************************************************/
+
+/// Converts a sequence of single line edits into operations. Deletes and inserts between two
+/// equal operations are combined into a single delete followed by a single insert.
+pub(crate) fn ops_from_tags(tags: &[Tag]) -> Vec<Op> {
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (0, 0);
+    let mut index = 0;
+
+    while index < tags.len() {
+        if tags[index] == Tag::Equal {
+            let start = index;
+            while index < tags.len() && tags[index] == Tag::Equal {
+                index += 1;
+            }
+            let len = index - start;
+            ops.push(Op::new(Tag::Equal, x..x + len, y..y + len));
+            x += len;
+            y += len;
+        } else {
+            let (mut deleted, mut inserted) = (0, 0);
+            while index < tags.len() && tags[index] != Tag::Equal {
+                if tags[index] == Tag::Delete {
+                    deleted += 1;
+                } else {
+                    inserted += 1;
+                }
+                index += 1;
+            }
+            if deleted > 0 {
+                ops.push(Op::new(Tag::Delete, x..x + deleted, y..y));
+            }
+            if inserted > 0 {
+                ops.push(Op::new(
+                    Tag::Insert,
+                    x + deleted..x + deleted,
+                    y..y + inserted,
+                ));
+            }
+            x += deleted;
+            y += inserted;
+        }
+    }
+
+    ops
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,516 @@
+//! The algorithms and options used to compute line differences.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::diff::*;
+use std::collections::HashMap;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The algorithm used to compute the differences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// The Myers algorithm, which finds a minimal edit script. This is the default.
+    Myers,
+    /// The patience algorithm, which anchors the diff on lines that are unique in both texts.
+    /// This gives more readable results for texts with many repeated lines, like braces or
+    /// blank lines.
+    Patience,
+    /// The histogram algorithm, which anchors the diff on the lines that occur the least. It is
+    /// an extension of the patience algorithm that also handles texts without unique lines.
+    Histogram,
+}
+
+/************************************************************************************************/
+
+/// The options used when computing the differences between lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// The algorithm used.
+    pub algorithm: Algorithm,
+    /// Ignores changes in the amount of whitespace when `true`.
+    pub ignore_whitespace: bool,
+    /// Hides changes that consist only of blank lines when `true`, see
+    /// `LineDiff::ignore_blank_lines`.
+    pub ignore_blank_lines: bool,
+    /// Ignores differences in case when `true`.
+    pub ignore_case: bool,
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl DiffOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options.
+    pub fn new() -> Self {
+        DiffOptions {
+            algorithm: Algorithm::Myers,
+            ignore_whitespace: false,
+            ignore_blank_lines: false,
+            ignore_case: false,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the algorithm used.
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether changes in the amount of whitespace are ignored.
+    pub fn ignore_whitespace(mut self, ignore_whitespace: bool) -> Self {
+        self.ignore_whitespace = ignore_whitespace;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether changes that consist only of blank lines are ignored.
+    pub fn ignore_blank_lines(mut self, ignore_blank_lines: bool) -> Self {
+        self.ignore_blank_lines = ignore_blank_lines;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether differences in case are ignored.
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn key(&self, line: &str) -> String {
+        let mut key = if self.ignore_whitespace {
+            line.split_whitespace().collect::<Vec<&str>>().join(" ")
+        } else {
+            String::from(line)
+        };
+
+        if self.ignore_blank_lines && key.trim().is_empty() {
+            key.clear();
+        }
+        if self.ignore_case {
+            key = key.to_lowercase();
+        }
+
+        key
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        DiffOptions::new()
+    }
+}
+
+/************************************************************************************************/
+
+impl LineDiff {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Computes the differences between two sets of lines with the given options. Lines that
+    /// are considered equal because of the options are tagged as equal, even when their text
+    /// differs. When blank lines are ignored, changes that consist only of blank lines are kept in
+    /// the edit script but hidden when rendering.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - the lines of the old text.
+    /// * `new` - the lines of the new text.
+    /// * `options` - the options used for comparing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    /// use rich_lib::lines::*;
+    ///
+    /// let old = String::from("a\nb\nc").split();
+    /// let new = String::from("b\nc\na").split();
+    /// let options = DiffOptions::new().algorithm(Algorithm::Patience);
+    /// let d = LineDiff::with_options(old, new, &options);
+    ///
+    /// assert_eq!(d.render(), vec!["-a", " b", " c", "+a"]);
+    /// ```
+    pub fn with_options(old: StringLines, new: StringLines, options: &DiffOptions) -> Self {
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        let mut intern = |line: &String| {
+            let next = ids.len();
+            *ids.entry(options.key(line)).or_insert(next)
+        };
+        let old_ids: Vec<usize> = old.iter().map(&mut intern).collect();
+        let new_ids: Vec<usize> = new.iter().map(&mut intern).collect();
+
+        let tags = match options.algorithm {
+            Algorithm::Myers => myers_tags(&old_ids, &new_ids),
+            Algorithm::Patience => patience_tags(&old_ids, &new_ids),
+            Algorithm::Histogram => histogram_tags(&old_ids, &new_ids),
+        };
+        let ops = ops_from_tags(&tags);
+
+        LineDiff {
+            old,
+            new,
+            ops,
+            ignore_blank_lines: options.ignore_blank_lines,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Computes the shortest edit script between two sequences with the Myers algorithm.
+pub(crate) fn myers<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Op> {
+    ops_from_tags(&myers_tags(old, new))
+}
+
+/************************************************************************************************/
+
+/// The maximum amount of occurrences of a line for it to be used as an anchor by the histogram
+/// algorithm.
+const MAX_OCCURRENCES: usize = 64;
+
+/************************************************************************************************/
+
+/// Computes the edits with the linear space variant of the Myers algorithm. The common prefix
+/// and suffix are matched first, the remainder is split on the middle snake of an optimal path
+/// and both halves are diffed recursively.
+fn myers_tags<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Tag> {
+    let mut tags = Vec::with_capacity(old.len() + new.len());
+    myers_split(old, new, &mut tags);
+    tags
+}
+
+/************************************************************************************************/
+
+fn myers_split<T: PartialEq>(old: &[T], new: &[T], tags: &mut Vec<Tag>) {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    tags.extend(std::iter::repeat_n(Tag::Equal, prefix));
+
+    match middle_snake(old_middle, new_middle) {
+        Some((x, y)) => {
+            myers_split(&old_middle[..x], &new_middle[..y], tags);
+            myers_split(&old_middle[x..], &new_middle[y..], tags);
+        }
+        None => {
+            tags.extend(std::iter::repeat_n(Tag::Delete, old_middle.len()));
+            tags.extend(std::iter::repeat_n(Tag::Insert, new_middle.len()));
+        }
+    }
+
+    tags.extend(std::iter::repeat_n(Tag::Equal, suffix));
+}
+
+/************************************************************************************************/
+
+/// Searches an optimal path from both ends at the same time, and returns the point where the
+/// forward and the reverse path meet. Only the furthest reaching paths of the current edit
+/// distance are kept, so the memory used is linear. Returns `None` when one of the sequences is
+/// empty, the edits are then trivial.
+fn middle_snake<T: PartialEq>(old: &[T], new: &[T]) -> Option<(usize, usize)> {
+    if old.is_empty() || new.is_empty() {
+        return None;
+    }
+
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d;
+    let length = (2 * max_d + 2) as usize;
+    let mut forward = vec![-1isize; length];
+    let mut reverse = vec![-1isize; length];
+    forward[(offset + 1) as usize] = 0;
+    reverse[(offset + 1) as usize] = 0;
+
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    // the diagonals that run off the grid are skipped
+    let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+
+    for d in 0..max_d {
+        for k1 in (-d + k1_start..=d - k1_end).step_by(2) {
+            let index = (offset + k1) as usize;
+            let mut x1 = if k1 == -d || (k1 != d && forward[index - 1] < forward[index + 1]) {
+                forward[index + 1]
+            } else {
+                forward[index - 1] + 1
+            };
+            let mut y1 = x1 - k1;
+            while x1 < n && y1 < m && old[x1 as usize] == new[y1 as usize] {
+                x1 += 1;
+                y1 += 1;
+            }
+            forward[index] = x1;
+
+            if x1 > n {
+                k1_end += 2;
+            } else if y1 > m {
+                k1_start += 2;
+            } else if odd {
+                let k2 = offset + delta - k1;
+                if k2 >= 0 && (k2 as usize) < length && reverse[k2 as usize] != -1 {
+                    let x2 = n - reverse[k2 as usize];
+                    if x1 >= x2 {
+                        return Some((x1 as usize, y1 as usize));
+                    }
+                }
+            }
+        }
+
+        for k2 in (-d + k2_start..=d - k2_end).step_by(2) {
+            let index = (offset + k2) as usize;
+            let mut x2 = if k2 == -d || (k2 != d && reverse[index - 1] < reverse[index + 1]) {
+                reverse[index + 1]
+            } else {
+                reverse[index - 1] + 1
+            };
+            let mut y2 = x2 - k2;
+            while x2 < n && y2 < m && old[(n - x2 - 1) as usize] == new[(m - y2 - 1) as usize] {
+                x2 += 1;
+                y2 += 1;
+            }
+            reverse[index] = x2;
+
+            if x2 > n {
+                k2_end += 2;
+            } else if y2 > m {
+                k2_start += 2;
+            } else if !odd {
+                let k1 = offset + delta - k2;
+                if k1 >= 0 && (k1 as usize) < length && forward[k1 as usize] != -1 {
+                    let x1 = forward[k1 as usize];
+                    let y1 = offset + x1 - k1;
+                    if x1 >= n - x2 {
+                        return Some((x1 as usize, y1 as usize));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/************************************************************************************************/
+
+fn patience_tags(old: &[usize], new: &[usize]) -> Vec<Tag> {
+    anchored_tags(old, new, &|old, new| {
+        let anchors = unique_anchors(old, new);
+        if anchors.is_empty() {
+            None
+        } else {
+            Some(anchors)
+        }
+    })
+}
+
+/************************************************************************************************/
+
+fn histogram_tags(old: &[usize], new: &[usize]) -> Vec<Tag> {
+    anchored_tags(old, new, &|old, new| {
+        rarest_region(old, new).map(|region| vec![region])
+    })
+}
+
+/************************************************************************************************/
+
+/// Diffs two sequences by matching their common prefix and suffix, and splitting the remainder
+/// on the anchors returned by the given function. Every anchor is a matching region given as the
+/// start in the old sequence, the start in the new sequence and the length. The parts between
+/// the anchors are diffed recursively, when there are no anchors the Myers algorithm is used.
+fn anchored_tags<F>(old: &[usize], new: &[usize], find_anchors: &F) -> Vec<Tag>
+where
+    F: Fn(&[usize], &[usize]) -> Option<Vec<(usize, usize, usize)>>,
+{
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut tags = vec![Tag::Equal; prefix];
+
+    if old_middle.is_empty() || new_middle.is_empty() {
+        tags.extend(std::iter::repeat_n(Tag::Delete, old_middle.len()));
+        tags.extend(std::iter::repeat_n(Tag::Insert, new_middle.len()));
+    } else {
+        match find_anchors(old_middle, new_middle) {
+            None => tags.append(&mut myers_tags(old_middle, new_middle)),
+            Some(anchors) => {
+                let (mut x, mut y) = (0, 0);
+                for (old_start, new_start, len) in anchors {
+                    tags.append(&mut anchored_tags(
+                        &old_middle[x..old_start],
+                        &new_middle[y..new_start],
+                        find_anchors,
+                    ));
+                    tags.extend(std::iter::repeat_n(Tag::Equal, len));
+                    x = old_start + len;
+                    y = new_start + len;
+                }
+                tags.append(&mut anchored_tags(
+                    &old_middle[x..],
+                    &new_middle[y..],
+                    find_anchors,
+                ));
+            }
+        }
+    }
+
+    tags.extend(std::iter::repeat_n(Tag::Equal, suffix));
+    tags
+}
+
+/************************************************************************************************/
+
+/// Returns the lines that occur exactly once in both sequences, reduced to the longest
+/// increasing subsequence so the anchors are in the same order in both sequences.
+fn unique_anchors(old: &[usize], new: &[usize]) -> Vec<(usize, usize, usize)> {
+    let mut counts: HashMap<usize, (usize, usize, usize)> = HashMap::new();
+
+    for (index, id) in old.iter().enumerate() {
+        let entry = counts.entry(*id).or_insert((0, 0, 0));
+        entry.0 += 1;
+        entry.2 = index;
+    }
+    for id in new.iter() {
+        if let Some(entry) = counts.get_mut(id) {
+            entry.1 += 1;
+        }
+    }
+
+    let candidates: Vec<(usize, usize)> = new
+        .iter()
+        .enumerate()
+        .filter_map(|(new_index, id)| match counts.get(id) {
+            Some((1, 1, old_index)) => Some((*old_index, new_index)),
+            _ => None,
+        })
+        .collect();
+
+    // patience sorting on the old indexes, ordered by the new indexes
+    let mut piles: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; candidates.len()];
+
+    for (index, (old_index, _)) in candidates.iter().enumerate() {
+        let pile = piles.partition_point(|top| candidates[*top].0 < *old_index);
+        previous[index] = pile.checked_sub(1).map(|pile| piles[pile]);
+        if pile == piles.len() {
+            piles.push(index);
+        } else {
+            piles[pile] = index;
+        }
+    }
+
+    let mut anchors = Vec::new();
+    let mut current = piles.last().copied();
+    while let Some(index) = current {
+        let (old_index, new_index) = candidates[index];
+        anchors.push((old_index, new_index, 1));
+        current = previous[index];
+    }
+
+    anchors.reverse();
+    anchors
+}
+
+/************************************************************************************************/
+
+/// Returns the matching region that contains the line with the lowest amount of occurrences in
+/// the old sequence, or the longest of these regions when there are more.
+fn rarest_region(old: &[usize], new: &[usize]) -> Option<(usize, usize, usize)> {
+    let mut positions: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (index, id) in old.iter().enumerate() {
+        positions.entry(*id).or_default().push(index);
+    }
+
+    let mut best: Option<(usize, (usize, usize, usize))> = None;
+    let mut new_index = 0;
+
+    while new_index < new.len() {
+        let mut next = new_index + 1;
+
+        if let Some(indexes) = positions.get(&new[new_index]) {
+            let occurrences = indexes.len();
+            if occurrences <= MAX_OCCURRENCES
+                && best.is_none_or(|(best_occurrences, _)| occurrences <= best_occurrences)
+            {
+                for old_index in indexes.iter() {
+                    let (mut old_start, mut new_start) = (*old_index, new_index);
+                    while old_start > 0 && new_start > 0 && old[old_start - 1] == new[new_start - 1]
+                    {
+                        old_start -= 1;
+                        new_start -= 1;
+                    }
+                    let mut len = new_index - new_start + 1;
+                    while old_start + len < old.len()
+                        && new_start + len < new.len()
+                        && old[old_start + len] == new[new_start + len]
+                    {
+                        len += 1;
+                    }
+
+                    let better = match best {
+                        None => true,
+                        Some((best_occurrences, (_, _, best_len))) => {
+                            occurrences < best_occurrences || len > best_len
+                        }
+                    };
+                    if better {
+                        best = Some((occurrences, (old_start, new_start, len)));
+                        next = next.max(new_start + len);
+                    }
+                }
+            }
+        }
+
+        new_index = next;
+    }
+
+    best.map(|(_, region)| region)
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,354 @@
+//! Word and character level differences within changed lines.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::color::Theme;
+use crate::diff::algorithm::myers;
+use crate::diff::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The granularity of the differences within a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    /// Lines are compared by words. A word is a run of alphanumeric characters or underscores,
+    /// a run of whitespace or a single other character.
+    Word,
+    /// Lines are compared by characters.
+    Char,
+}
+
+/************************************************************************************************/
+
+/// A part of a line that is equal, deleted or inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The kind of edit.
+    pub tag: Tag,
+    /// The text of the token.
+    pub text: String,
+}
+
+/************************************************************************************************/
+
+/// A line of a line diff with the differences within the line. A changed line has both an old
+/// and a new index, a deleted line has only an old index and an inserted line has only a new
+/// index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineLine {
+    /// The index of the line in the old text.
+    pub old: Option<usize>,
+    /// The index of the line in the new text.
+    pub new: Option<usize>,
+    /// The parts of the line.
+    pub tokens: Vec<Token>,
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Token {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a new token.
+    pub fn new(tag: Tag, text: &str) -> Self {
+        Token {
+            tag,
+            text: String::from(text),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl LineDiff {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the indexes of the old and new lines that are paired as changed lines. Within a
+    /// change, deleted and inserted lines are paired in order. Moved lines are never paired.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("line0\nline1\nline2");
+    /// let new = String::from("line0\nlineA\nline2\nline3");
+    ///
+    /// assert_eq!(old.diff(&new).paired_lines(), vec![(1, 1)]);
+    /// ```
+    pub fn paired_lines(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+
+        for ops in self.shown_ops().windows(2) {
+            if ops[0].tag == Tag::Delete
+                && ops[1].tag == Tag::Insert
+                && ops[0].moved.is_none()
+                && ops[1].moved.is_none()
+            {
+                pairs.extend(ops[0].old.clone().zip(ops[1].new.clone()));
+            }
+        }
+
+        pairs
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns all lines of the diff with the differences within the paired changed lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `granularity` - compares changed lines by words or by characters.
+    pub fn inline(&self, granularity: Granularity) -> Vec<InlineLine> {
+        self.rows()
+            .iter()
+            .map(|row| {
+                let tokens = match (row.old, row.new) {
+                    (Some(old), _) if row.equal => vec![Token::new(Tag::Equal, &self.old[old])],
+                    (Some(old), Some(new)) => {
+                        diff_tokens(&self.old[old], &self.new[new], granularity)
+                    }
+                    (Some(old), None) => vec![Token::new(Tag::Delete, &self.old[old])],
+                    (None, Some(new)) => vec![Token::new(Tag::Insert, &self.new[new])],
+                    (None, None) => Vec::new(),
+                };
+                InlineLine {
+                    old: row.old,
+                    new: row.new,
+                    tokens,
+                }
+            })
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders all lines of the diff with the differences marked inline, deleted parts as
+    /// `[-text-]` and inserted parts as `{+text+}`.
+    ///
+    /// # Arguments
+    ///
+    /// * `granularity` - compares changed lines by words or by characters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("let x = 1;\nprint(x);");
+    /// let new = String::from("let y = 1;\nprint(x);");
+    ///
+    /// assert_eq!(
+    ///     old.diff(&new).render_inline(Granularity::Word),
+    ///     vec!["let [-x-]{+y+} = 1;", "print(x);"]
+    /// );
+    /// ```
+    pub fn render_inline(&self, granularity: Granularity) -> StringLines {
+        self.inline(granularity)
+            .iter()
+            .map(|line| render_tokens(&line.tokens))
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders all lines of the diff with the differences coloured inline instead of marked.
+    /// Within changed lines the deleted and inserted parts are highlighted, deleted and inserted
+    /// lines are coloured as a whole and equal lines are rendered in the context style.
+    ///
+    /// # Arguments
+    ///
+    /// * `granularity` - compares changed lines by words or by characters.
+    /// * `theme` - the styles of the parts.
+    pub fn render_inline_colored(&self, granularity: Granularity, theme: &Theme) -> StringLines {
+        self.inline(granularity)
+            .iter()
+            .map(|line| {
+                let paired = line.old.is_some() && line.new.is_some();
+                let equal = line.tokens.iter().all(|token| token.tag == Tag::Equal);
+                line.tokens
+                    .iter()
+                    .map(|token| match token.tag {
+                        Tag::Equal if equal => theme.context.paint(&token.text),
+                        Tag::Equal => token.text.clone(),
+                        Tag::Delete if paired => theme.deleted_highlight.paint(&token.text),
+                        Tag::Delete => theme.deleted.paint(&token.text),
+                        Tag::Insert if paired => theme.inserted_highlight.paint(&token.text),
+                        Tag::Insert => theme.inserted.paint(&token.text),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Computes the word level differences between two lines.
+///
+/// # Arguments
+///
+/// * `old` - the old line.
+/// * `new` - the new line.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::diff::*;
+///
+/// let tokens = diff_words("one two", "one three");
+///
+/// assert_eq!(
+///     tokens,
+///     vec![
+///         Token::new(Tag::Equal, "one "),
+///         Token::new(Tag::Delete, "two"),
+///         Token::new(Tag::Insert, "three")
+///     ]
+/// );
+/// ```
+pub fn diff_words(old: &str, new: &str) -> Vec<Token> {
+    diff_tokens(old, new, Granularity::Word)
+}
+
+/************************************************************************************************/
+
+/// Computes the character level differences between two lines.
+///
+/// # Arguments
+///
+/// * `old` - the old line.
+/// * `new` - the new line.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::diff::*;
+///
+/// assert_eq!(render_tokens(&diff_chars("colour", "color")), "colo[-u-]r");
+/// ```
+pub fn diff_chars(old: &str, new: &str) -> Vec<Token> {
+    diff_tokens(old, new, Granularity::Char)
+}
+
+/************************************************************************************************/
+
+/// Renders tokens with deleted parts marked as `[-text-]` and inserted parts as `{+text+}`.
+///
+/// # Arguments
+///
+/// * `tokens` - the tokens to render.
+pub fn render_tokens(tokens: &[Token]) -> String {
+    let mut result = String::new();
+
+    for token in tokens.iter() {
+        match token.tag {
+            Tag::Equal => result.push_str(&token.text),
+            Tag::Delete => result.push_str(&format!("[-{}-]", token.text)),
+            Tag::Insert => result.push_str(&format!("{{+{}+}}", token.text)),
+        }
+    }
+
+    result
+}
+
+/************************************************************************************************/
+
+/// Paints the tokens of one side of a changed line. Equal parts get the style of the side and
+/// changed parts its highlight style, the parts of the other side are left out.
+pub(crate) fn paint_tokens(tokens: &[Token], side: Tag, theme: &Theme) -> String {
+    let (style, highlight) = if side == Tag::Insert {
+        (&theme.inserted, &theme.inserted_highlight)
+    } else {
+        (&theme.deleted, &theme.deleted_highlight)
+    };
+
+    tokens
+        .iter()
+        .filter(|token| token.tag == Tag::Equal || token.tag == side)
+        .map(|token| match token.tag {
+            Tag::Equal => style.paint(&token.text),
+            _ => highlight.paint(&token.text),
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+fn diff_tokens(old: &str, new: &str, granularity: Granularity) -> Vec<Token> {
+    let old = split_tokens(old, granularity);
+    let new = split_tokens(new, granularity);
+
+    myers(&old, &new)
+        .iter()
+        .map(|op| match op.tag {
+            Tag::Equal | Tag::Delete => Token::new(op.tag, &old[op.old.clone()].concat()),
+            Tag::Insert => Token::new(op.tag, &new[op.new.clone()].concat()),
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+fn split_tokens(line: &str, granularity: Granularity) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<CharClass> = None;
+
+    for (index, c) in line.char_indices() {
+        let class = match granularity {
+            Granularity::Char => CharClass::Other,
+            Granularity::Word => CharClass::of(c),
+        };
+        if index > 0 && (class == CharClass::Other || previous != Some(class)) {
+            tokens.push(&line[start..index]);
+            start = index;
+        }
+        previous = Some(class);
+    }
+
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+
+    tokens
+}
+
+/************************************************************************************************/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Word,
+    Space,
+    Other,
+}
+
+/************************************************************************************************/
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_alphanumeric() || c == '_' {
+            CharClass::Word
+        } else if c.is_whitespace() {
+            CharClass::Space
+        } else {
+            CharClass::Other
+        }
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
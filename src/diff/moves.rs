@@ -0,0 +1,285 @@
+//! Detection of blocks of lines that are moved between two texts.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::diff::algorithm::myers;
+use crate::diff::*;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The options used when detecting moved lines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveOptions {
+    /// The minimum amount of lines of a moved block, `3` by default.
+    pub min_lines: usize,
+    /// The minimum similarity of a deleted and an inserted line for them to be considered the
+    /// same line, from `0.0` to `1.0`. The default `1.0` only accepts equal lines. A block of
+    /// similar lines needs at least one pair of lines that are equal apart from whitespace.
+    pub similarity: f64,
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl MoveOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options.
+    pub fn new() -> Self {
+        MoveOptions {
+            min_lines: 3,
+            similarity: 1.0,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the minimum amount of lines of a moved block.
+    pub fn min_lines(mut self, min_lines: usize) -> Self {
+        self.min_lines = min_lines;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the minimum similarity of two lines for them to be considered the same line.
+    pub fn similarity(mut self, similarity: f64) -> Self {
+        self.similarity = similarity;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn matches(&self, old: &str, new: &str) -> bool {
+        old == new || (self.similarity < 1.0 && similarity(old, new) >= self.similarity)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the key of a line used to find candidate matches, the line itself or the line
+    /// with collapsed whitespace when similar lines are accepted.
+    fn key(&self, line: &str) -> String {
+        if self.similarity < 1.0 {
+            line.split_whitespace().collect::<Vec<_>>().join(" ")
+        } else {
+            String::from(line)
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for MoveOptions {
+    fn default() -> Self {
+        MoveOptions::new()
+    }
+}
+
+/************************************************************************************************/
+
+impl LineDiff {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Detects blocks of deleted lines that are inserted at another position. The lines of such
+    /// a block are split from their delete and insert operations into operations that share the
+    /// same move id. The ids are numbered in the order of the blocks in the old text. The longest
+    /// blocks are matched first, a deleted line is never matched with the inserted lines of the
+    /// same change.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for detecting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("[a]\nx=1\ny=2\n[b]\nz=3");
+    /// let new = String::from("[b]\nz=3\n[a]\nx=1\ny=2");
+    /// let d = old.diff(&new).detect_moves(&MoveOptions::new().min_lines(2));
+    ///
+    /// assert_eq!(
+    ///     d.render(),
+    ///     vec![">[b]", ">z=3", " [a]", " x=1", " y=2", "<[b]", "<z=3"]
+    /// );
+    /// ```
+    pub fn detect_moves(mut self, options: &MoveOptions) -> Self {
+        let mut old_used = vec![false; self.old.len()];
+        let mut new_used = vec![false; self.new.len()];
+        let mut blocks = Vec::new();
+
+        while let Some((old_start, new_start, len)) =
+            self.longest_move(&old_used, &new_used, options)
+        {
+            if len < options.min_lines.max(1) {
+                break;
+            }
+            old_used[old_start..old_start + len].fill(true);
+            new_used[new_start..new_start + len].fill(true);
+            blocks.push((old_start, new_start, len));
+        }
+
+        blocks.sort_unstable();
+
+        let mut old_moves = vec![None; self.old.len()];
+        let mut new_moves = vec![None; self.new.len()];
+        for (id, (old_start, new_start, len)) in blocks.into_iter().enumerate() {
+            old_moves[old_start..old_start + len].fill(Some(id));
+            new_moves[new_start..new_start + len].fill(Some(id));
+        }
+
+        let mut ops = Vec::with_capacity(self.ops.len());
+        for op in self.ops.iter() {
+            match op.tag {
+                Tag::Equal => ops.push(Op::new(Tag::Equal, op.old.clone(), op.new.clone())),
+                Tag::Delete => ops.extend(runs(op.old.clone(), &old_moves).into_iter().map(
+                    |(old, moved)| Op {
+                        tag: Tag::Delete,
+                        old,
+                        new: op.new.clone(),
+                        moved,
+                    },
+                )),
+                Tag::Insert => ops.extend(runs(op.new.clone(), &new_moves).into_iter().map(
+                    |(new, moved)| Op {
+                        tag: Tag::Insert,
+                        old: op.old.clone(),
+                        new,
+                        moved,
+                    },
+                )),
+            }
+        }
+
+        self.ops = ops;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the longest block of matching deleted and inserted lines that are not used yet,
+    /// as the start in the old text, the start in the new text and the length. The inserted
+    /// lines are indexed by their key, blocks are only extended from pairs with the same key.
+    fn longest_move(
+        &self,
+        old_used: &[bool],
+        new_used: &[bool],
+        options: &MoveOptions,
+    ) -> Option<(usize, usize, usize)> {
+        let deleted: Vec<&Op> = self.ops.iter().filter(|op| op.tag == Tag::Delete).collect();
+        let inserted: Vec<&Op> = self.ops.iter().filter(|op| op.tag == Tag::Insert).collect();
+
+        let mut candidates: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for (insert_index, op) in inserted.iter().enumerate() {
+            for new in op.new.clone().filter(|new| !new_used[*new]) {
+                candidates
+                    .entry(options.key(&self.new[new]))
+                    .or_default()
+                    .push((insert_index, new));
+            }
+        }
+
+        let fits = |old: usize, new: usize| {
+            !old_used[old] && !new_used[new] && options.matches(&self.old[old], &self.new[new])
+        };
+        let mut best = None;
+
+        for (delete_index, op) in deleted.iter().enumerate() {
+            for old in op.old.clone().filter(|old| !old_used[*old]) {
+                let matches = match candidates.get(&options.key(&self.old[old])) {
+                    Some(matches) => matches,
+                    None => continue,
+                };
+
+                for &(insert_index, new) in matches {
+                    let (old_range, new_range) = (&op.old, &inserted[insert_index].new);
+                    if inserted[insert_index].old.start == old_range.end {
+                        continue;
+                    }
+
+                    let before = (1..)
+                        .take_while(|offset| {
+                            old >= old_range.start + offset
+                                && new >= new_range.start + offset
+                                && fits(old - offset, new - offset)
+                        })
+                        .count();
+                    let after = (0..)
+                        .take_while(|offset| {
+                            old + offset < old_range.end
+                                && new + offset < new_range.end
+                                && fits(old + offset, new + offset)
+                        })
+                        .count();
+
+                    let block = (
+                        before + after,
+                        Reverse((delete_index, insert_index, old - before, new - before)),
+                    );
+                    if best.as_ref().is_none_or(|best| block > *best) {
+                        best = Some(block);
+                    }
+                }
+            }
+        }
+
+        best.map(|(len, Reverse((_, _, old, new)))| (old, new, len))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns the similarity of two lines as twice the amount of equal characters divided by the
+/// total amount of characters.
+fn similarity(old: &str, new: &str) -> f64 {
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = new.chars().collect();
+    let total = old.len() + new.len();
+
+    if total == 0 {
+        return 1.0;
+    }
+
+    let equal: usize = myers(&old, &new)
+        .iter()
+        .filter(|op| op.tag == Tag::Equal)
+        .map(|op| op.old.len())
+        .sum();
+
+    (2 * equal) as f64 / total as f64
+}
+
+/************************************************************************************************/
+
+/// Splits a range of lines into runs of lines with the same move id.
+fn runs(range: Range<usize>, moves: &[Option<usize>]) -> Vec<(Range<usize>, Option<usize>)> {
+    let mut result: Vec<(Range<usize>, Option<usize>)> = Vec::new();
+
+    for index in range {
+        match result.last_mut() {
+            Some((run, moved)) if *moved == moves[index] => run.end = index + 1,
+            _ => result.push((index..index + 1, moves[index])),
+        }
+    }
+
+    result
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
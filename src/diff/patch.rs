@@ -0,0 +1,890 @@
+//! Machine-readable edit scripts that can be stored, parsed and applied, as JSON or as an `ed`
+//! script.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::diff::*;
+use std::fmt;
+use std::ops::Range;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// A single change of a patch, the lines of the old text in a range are replaced by new lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// The range of replaced lines in the old text. An empty range inserts the lines before the
+    /// line at its start.
+    pub old: Range<usize>,
+    /// The replaced lines when they are known, these are verified when applying the patch. An
+    /// `ed` script does not contain the replaced lines.
+    pub deleted: Option<StringLines>,
+    /// The new lines.
+    pub inserted: StringLines,
+}
+
+/************************************************************************************************/
+
+/// A list of changes that turns an old text into a new text. The changes are ordered and do
+/// not overlap, their ranges refer to the lines of the old text.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Patch {
+    /// The changes.
+    pub changes: Vec<Change>,
+}
+
+/************************************************************************************************/
+
+/// The error returned when parsing or applying a patch fails. Line numbers are one based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The patch is invalid.
+    Parse {
+        /// The line in the patch, or `0` when the problem is not on a specific line.
+        line: usize,
+        /// The description of the problem.
+        message: String,
+    },
+    /// A change refers to a line after the end of the text.
+    OutOfRange {
+        /// The line in the text.
+        line: usize,
+    },
+    /// A line in the text differs from the line replaced by a change.
+    Mismatch {
+        /// The line in the text.
+        line: usize,
+    },
+}
+
+/************************************************************************************************/
+
+/// The `ApplyPatch` trait contains all functions to be implemented that apply a patch to lines.
+pub trait ApplyPatch: Sized {
+    /// Applies the changes of a patch. The replaced lines of the changes are verified when they
+    /// are known.
+    ///
+    /// # Arguments
+    ///
+    /// * `patch` - the patch to apply.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("line0\nline1\nline2");
+    /// let new = String::from("line0\nlineA\nline2\nline3");
+    /// let patch = old.diff(&new).to_patch();
+    ///
+    /// assert_eq!(old.apply_patch(&patch), Ok(new));
+    /// ```
+    fn apply_patch(self, patch: &Patch) -> Result<Self, PatchError>;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Change {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a new change without the replaced lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - the range of replaced lines in the old text.
+    /// * `inserted` - the new lines.
+    pub fn new(old: Range<usize>, inserted: StringLines) -> Self {
+        Change {
+            old,
+            deleted: None,
+            inserted,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Patch {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a new patch.
+    ///
+    /// # Arguments
+    ///
+    /// * `changes` - the ordered, non overlapping changes.
+    pub fn new(changes: Vec<Change>) -> Self {
+        Patch { changes }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the patch as JSON. The structure is an object with a `version` (currently `1`)
+    /// and a list of `changes`. Every change contains the `old` range as a (zero based) start
+    /// and end, the `inserted` lines and, when known, the `deleted` lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("line0\nline1\nline2");
+    /// let new = String::from("line0\nlineA\nline2");
+    ///
+    /// assert_eq!(
+    ///     old.diff(&new).to_patch().to_json(),
+    ///     concat!(
+    ///         "{\n",
+    ///         "  \"version\": 1,\n",
+    ///         "  \"changes\": [\n",
+    ///         "    {\"old\": [1, 2], \"deleted\": [\"line1\"], \"inserted\": [\"lineA\"]}\n",
+    ///         "  ]\n",
+    ///         "}"
+    ///     )
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let changes: StringLines = self
+            .changes
+            .iter()
+            .map(|change| {
+                let deleted = match &change.deleted {
+                    Some(deleted) => format!(", \"deleted\": {}", json_lines(deleted)),
+                    None => String::new(),
+                };
+                format!(
+                    "    {{\"old\": [{}, {}]{}, \"inserted\": {}}}",
+                    change.old.start,
+                    change.old.end,
+                    deleted,
+                    json_lines(&change.inserted)
+                )
+            })
+            .collect();
+
+        if changes.is_empty() {
+            String::from("{\n  \"version\": 1,\n  \"changes\": []\n}")
+        } else {
+            format!(
+                "{{\n  \"version\": 1,\n  \"changes\": [\n{}\n  ]\n}}",
+                changes.join(",\n")
+            )
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Parses a patch from JSON in the structure written by `to_json`. Unknown fields are
+    /// ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - the JSON text.
+    pub fn from_json(text: &str) -> Result<Self, PatchError> {
+        let mut parser = JsonParser { text, position: 0 };
+        let root = parser.parse()?;
+        let error = |message: &str| PatchError::Parse {
+            line: 0,
+            message: String::from(message),
+        };
+
+        match root.field("version") {
+            Some(Json::Number(version)) if *version == 1.0 => {}
+            Some(_) => return Err(error("unsupported version")),
+            None => return Err(error("missing version")),
+        }
+
+        let changes = match root.field("changes") {
+            Some(Json::Array(changes)) => changes,
+            _ => return Err(error("missing changes")),
+        };
+        let mut patch = Patch::new(Vec::new());
+
+        for change in changes.iter() {
+            let old = match change.field("old") {
+                Some(Json::Array(range)) if range.len() == 2 => {
+                    match (range[0].index(), range[1].index()) {
+                        (Some(start), Some(end)) if start <= end => start..end,
+                        _ => return Err(error("invalid range")),
+                    }
+                }
+                _ => return Err(error("missing range")),
+            };
+            let deleted = match change.field("deleted") {
+                None | Some(Json::Null) => None,
+                Some(lines) => Some(lines.lines().ok_or_else(|| error("invalid lines"))?),
+            };
+            let inserted = match change.field("inserted") {
+                None | Some(Json::Null) => StringLines::new(),
+                Some(lines) => lines.lines().ok_or_else(|| error("invalid lines"))?,
+            };
+
+            if deleted
+                .as_ref()
+                .is_some_and(|deleted| deleted.len() != old.len())
+            {
+                return Err(error("deleted lines do not match the range"));
+            }
+            patch
+                .push(Change {
+                    old,
+                    deleted,
+                    inserted,
+                })
+                .map_err(|message| error(&message))?;
+        }
+
+        Ok(patch)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the patch as an `ed` script, like `diff -e`. The commands are ordered from the
+    /// end of the text to the start, so the line numbers of every command refer to the old text.
+    /// A new line that is a single `.` is written as `..` and fixed with a `s/.//` command.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("line0\nline1\nline2\nline3");
+    /// let new = String::from("line0\nlineA\nline2");
+    ///
+    /// assert_eq!(old.diff(&new).to_patch().to_ed(), "4d\n2c\nlineA\n.");
+    /// ```
+    pub fn to_ed(&self) -> String {
+        let mut result = StringLines::new();
+
+        for change in self.changes.iter().rev() {
+            let range = ed_range(&change.old);
+
+            if change.inserted.is_empty() {
+                result.push(format!("{}d", range));
+                continue;
+            }
+
+            let mut command = if change.old.is_empty() {
+                format!("{}a", change.old.start)
+            } else {
+                format!("{}c", range)
+            };
+            let mut position = change.old.start;
+            let mut lines = change.inserted.iter().peekable();
+
+            while lines.peek().is_some() {
+                result.push(command);
+
+                let mut dot = false;
+                for line in lines.by_ref() {
+                    position += 1;
+                    if line == "." {
+                        result.push(String::from(".."));
+                        dot = true;
+                        break;
+                    }
+                    result.push(line.clone());
+                }
+
+                result.push(String::from("."));
+                if dot {
+                    result.push(String::from("s/.//"));
+                }
+                command = format!("{}a", position);
+            }
+        }
+
+        result.merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Parses a patch from an `ed` script in the form written by `diff -e`: the commands `a`,
+    /// `c` and `d` ordered from the end of the text to the start, and `s/.//` after a new line
+    /// written as `..`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - the `ed` script.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let patch = Patch::from_ed("4d\n2c\nlineA\n.").unwrap();
+    /// let s = String::from("line0\nline1\nline2\nline3").apply_patch(&patch);
+    ///
+    /// assert_eq!(s, Ok(String::from("line0\nlineA\nline2")));
+    /// ```
+    pub fn from_ed(text: &str) -> Result<Self, PatchError> {
+        let mut changes: Vec<Change> = Vec::new();
+        let mut lines = text.lines().enumerate();
+        let mut fixed = false;
+
+        while let Some((index, line)) = lines.next() {
+            let error = |message: &str| PatchError::Parse {
+                line: index + 1,
+                message: String::from(message),
+            };
+
+            if line == "s/.//" {
+                let last = changes
+                    .last_mut()
+                    .and_then(|change| change.inserted.last_mut())
+                    .filter(|last| last.starts_with('.'))
+                    .ok_or_else(|| error("nothing to substitute"))?;
+                last.remove(0);
+                fixed = true;
+                continue;
+            }
+
+            let (old, command) = parse_ed_command(line).ok_or_else(|| error("invalid command"))?;
+            let mut inserted = StringLines::new();
+
+            if command != 'd' {
+                loop {
+                    match lines.next() {
+                        Some((_, ".")) => break,
+                        Some((_, line)) => inserted.push(String::from(line)),
+                        None => return Err(error("missing end of text")),
+                    }
+                }
+            }
+
+            let continued = changes.last().filter(|last| {
+                fixed && command == 'a' && old.start == last.old.start + last.inserted.len()
+            });
+            fixed = false;
+
+            if continued.is_some() {
+                if let Some(last) = changes.last_mut() {
+                    last.inserted.append(&mut inserted);
+                }
+            } else if changes.last().is_some_and(|last| old.end > last.old.start) {
+                return Err(error("commands are not ordered from the end to the start"));
+            } else {
+                changes.push(Change::new(old, inserted));
+            }
+        }
+
+        changes.reverse();
+        Ok(Patch::new(changes))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn push(&mut self, change: Change) -> Result<(), String> {
+        if self
+            .changes
+            .last()
+            .is_some_and(|last| change.old.start < last.old.end)
+        {
+            return Err(String::from("changes are not ordered"));
+        }
+
+        self.changes.push(change);
+        Ok(())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::Parse { line: 0, message } => write!(f, "invalid patch: {}", message),
+            PatchError::Parse { line, message } => {
+                write!(f, "invalid patch on line {}: {}", line, message)
+            }
+            PatchError::OutOfRange { line } => write!(f, "line {} is out of range", line),
+            PatchError::Mismatch { line } => write!(f, "line {} does not match", line),
+        }
+    }
+}
+
+/************************************************************************************************/
+
+impl std::error::Error for PatchError {}
+
+/************************************************************************************************/
+
+impl LineDiff {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the edit script as a patch. Adjacent deleted and inserted lines are combined into
+    /// a single change that contains the replaced lines. Equal lines that differ, because the
+    /// diff ignores whitespace or case, are replaced as well, so applying the patch to the old
+    /// lines always gives the new lines.
+    pub fn to_patch(&self) -> Patch {
+        let mut changes: Vec<Change> = Vec::new();
+        let mut open = false;
+
+        for op in self.ops.iter() {
+            match op.tag {
+                Tag::Equal => {
+                    let old = &self.old[op.old.clone()];
+                    let new = &self.new[op.new.clone()];
+                    for (offset, (old, new)) in old.iter().zip(new).enumerate() {
+                        if old == new {
+                            open = false;
+                        } else {
+                            let line = op.old.start + offset;
+                            extend_changes(&mut changes, open, line..line + 1, &[old], &[new]);
+                            open = true;
+                        }
+                    }
+                }
+                Tag::Delete => {
+                    let deleted: Vec<&String> = self.old[op.old.clone()].iter().collect();
+                    extend_changes(&mut changes, open, op.old.clone(), &deleted, &[]);
+                    open = true;
+                }
+                Tag::Insert => {
+                    let inserted: Vec<&String> = self.new[op.new.clone()].iter().collect();
+                    extend_changes(&mut changes, open, op.old.clone(), &[], &inserted);
+                    open = true;
+                }
+            }
+        }
+
+        Patch::new(changes)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl ApplyPatch for StringLines {
+    fn apply_patch(mut self, patch: &Patch) -> Result<Self, PatchError> {
+        for change in patch.changes.iter().rev() {
+            if change.old.end > self.len() {
+                return Err(PatchError::OutOfRange {
+                    line: change.old.end,
+                });
+            }
+            if let Some(deleted) = &change.deleted {
+                let actual = &self[change.old.clone()];
+                if let Some(offset) = (0..deleted.len()).find(|i| actual.get(*i) != deleted.get(*i))
+                {
+                    return Err(PatchError::Mismatch {
+                        line: change.old.start + offset + 1,
+                    });
+                }
+            }
+
+            self.splice(change.old.clone(), change.inserted.iter().cloned());
+        }
+
+        Ok(self)
+    }
+}
+
+/************************************************************************************************/
+
+impl ApplyPatch for String {
+    fn apply_patch(self, patch: &Patch) -> Result<Self, PatchError> {
+        Ok(self.split().apply_patch(patch)?.merge())
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// A parsed JSON value, the value of a boolean is not needed for a patch.
+enum Json {
+    Null,
+    Bool,
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/************************************************************************************************/
+
+/// A minimal JSON parser, which reads the values of a patch.
+struct JsonParser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+/************************************************************************************************/
+
+impl Json {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn field(&self, name: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn index(&self) -> Option<usize> {
+        match self {
+            Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+            _ => None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn lines(&self) -> Option<StringLines> {
+        match self {
+            Json::Array(values) => values
+                .iter()
+                .map(|value| match value {
+                    Json::String(s) => Some(s.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl<'a> JsonParser<'a> {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn parse(&mut self) -> Result<Json, PatchError> {
+        let value = self.value()?;
+
+        self.skip_whitespace();
+        if self.position < self.text.len() {
+            return Err(self.error("unexpected text after the value"));
+        }
+
+        Ok(value)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn value(&mut self) -> Result<Json, PatchError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('t') => self.keyword("true", Json::Bool),
+            Some('f') => self.keyword("false", Json::Bool),
+            Some('n') => self.keyword("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn object(&mut self) -> Result<Json, PatchError> {
+        let mut fields = Vec::new();
+
+        self.expect('{')?;
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Json::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Json::Object(fields)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn array(&mut self) -> Result<Json, PatchError> {
+        let mut values = Vec::new();
+
+        self.expect('[')?;
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Json::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Json::Array(values)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn string(&mut self) -> Result<String, PatchError> {
+        let mut result = String::new();
+
+        self.expect('"')?;
+        loop {
+            match self.next() {
+                Some('"') => return Ok(result),
+                Some('\\') => {
+                    let c = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    result.push(c);
+                }
+                Some(c) if (c as u32) < 0x20 => return Err(self.error("unescaped control")),
+                Some(c) => result.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn unicode_escape(&mut self) -> Result<char, PatchError> {
+        let high = self.hex()?;
+
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if self.next() != Some('\\') || self.next() != Some('u') {
+                return Err(self.error("invalid surrogate pair"));
+            }
+            let low = self.hex()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("invalid surrogate pair"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn hex(&mut self) -> Result<u32, PatchError> {
+        let digits = self
+            .text
+            .get(self.position..self.position + 4)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        let value =
+            u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+
+        self.position += 4;
+        Ok(value)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn number(&mut self) -> Result<Json, PatchError> {
+        let start = self.position;
+
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(c))
+        {
+            self.position += 1;
+        }
+
+        self.text[start..self.position]
+            .parse::<f64>()
+            .map(Json::Number)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn keyword(&mut self, keyword: &str, value: Json) -> Result<Json, PatchError> {
+        if self.text[self.position..].starts_with(keyword) {
+            self.position += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn expect(&mut self, expected: char) -> Result<(), PatchError> {
+        if self.next() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", expected)))
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| " \t\r\n".contains(c)) {
+            self.position += 1;
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn error(&self, message: &str) -> PatchError {
+        let end = self.position.min(self.text.len());
+
+        PatchError::Parse {
+            line: self.text.as_bytes()[..end]
+                .iter()
+                .filter(|b| **b == b'\n')
+                .count()
+                + 1,
+            message: String::from(message),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Extends the last change with replaced lines when it is still open, starts a new change
+/// otherwise.
+fn extend_changes(
+    changes: &mut Vec<Change>,
+    open: bool,
+    old: Range<usize>,
+    deleted: &[&String],
+    inserted: &[&String],
+) {
+    if !open || changes.is_empty() {
+        changes.push(Change {
+            old: old.start..old.start,
+            deleted: Some(StringLines::new()),
+            inserted: StringLines::new(),
+        });
+    }
+    if let Some(change) = changes.last_mut() {
+        change.old.end = old.end;
+        if let Some(lines) = change.deleted.as_mut() {
+            lines.extend(deleted.iter().map(|line| String::from(line.as_str())));
+        }
+        change
+            .inserted
+            .extend(inserted.iter().map(|line| String::from(line.as_str())));
+    }
+}
+
+/************************************************************************************************/
+
+/// Returns lines as a JSON array of strings.
+fn json_lines(lines: &[String]) -> String {
+    let strings: StringLines = lines.iter().map(|line| json_string(line)).collect();
+    format!("[{}]", strings.join(", "))
+}
+
+/************************************************************************************************/
+
+/// Returns a string as a JSON string, with the quotes and control characters escaped.
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+
+    result
+}
+
+/************************************************************************************************/
+
+/// Returns a range of lines as an `ed` address, with one based line numbers.
+fn ed_range(range: &Range<usize>) -> String {
+    if range.len() <= 1 {
+        format!("{}", range.start + 1)
+    } else {
+        format!("{},{}", range.start + 1, range.end)
+    }
+}
+
+/************************************************************************************************/
+
+/// Parses an `a`, `c` or `d` command of an `ed` script into the range of lines it replaces.
+fn parse_ed_command(line: &str) -> Option<(Range<usize>, char)> {
+    let command = line.chars().last()?;
+    let address = &line[..line.len() - command.len_utf8()];
+    let (first, last) = match address.split_once(',') {
+        Some((first, last)) => (first.parse::<usize>().ok()?, last.parse::<usize>().ok()?),
+        None => {
+            let line = address.parse::<usize>().ok()?;
+            (line, line)
+        }
+    };
+
+    match command {
+        'a' if first == last => Some((first..first, command)),
+        'c' | 'd' if first > 0 && first <= last => Some((first - 1..last, command)),
+        _ => None,
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
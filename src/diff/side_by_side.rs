@@ -0,0 +1,380 @@
+//! Side-by-side rendering of line differences, like `diff -y` or `sdiff`.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::color::{Style, Theme};
+use crate::diff::*;
+use crate::gutter::digits;
+use crate::tabs::*;
+use crate::width::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The way lines that do not fit in a column are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Lines are cut off at the width of the column.
+    Truncate,
+    /// Lines are continued on the next row.
+    Wrap,
+}
+
+/************************************************************************************************/
+
+/// The options used when rendering a diff side-by-side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideBySideOptions {
+    /// The total width of a rendered row.
+    pub width: usize,
+    /// The way lines that do not fit in a column are rendered.
+    pub overflow: Overflow,
+    /// Shows the line numbers of both sides when `true`.
+    pub line_numbers: bool,
+    /// Leaves out the equal lines when `true`.
+    pub suppress_common: bool,
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl SideBySideOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options for a given total width.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the total width of a rendered row.
+    pub fn new(width: usize) -> Self {
+        SideBySideOptions {
+            width,
+            overflow: Overflow::Truncate,
+            line_numbers: false,
+            suppress_common: false,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the way lines that do not fit in a column are rendered.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether line numbers are shown.
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether equal lines are left out.
+    pub fn suppress_common(mut self, suppress_common: bool) -> Self {
+        self.suppress_common = suppress_common;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl LineDiff {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the old and new lines in two columns. The gutter between the columns contains `|`
+    /// for changed lines, `<` for deleted lines and `>` for inserted lines. Moved lines are marked
+    /// with `(` where they are moved from and with `)` where they are moved to. Tabs are expanded
+    /// to eight columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for rendering.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::diff::*;
+    ///
+    /// let old = String::from("same\nold line\nremoved");
+    /// let new = String::from("same\nnew line\nadded\nmore");
+    /// let options = SideBySideOptions::new(21).suppress_common(true);
+    ///
+    /// assert_eq!(
+    ///     old.diff(&new).render_side_by_side(&options),
+    ///     vec![
+    ///         "old line  | new line",
+    ///         "removed   | added",
+    ///         "          > more",
+    ///     ]
+    /// );
+    /// ```
+    pub fn render_side_by_side(&self, options: &SideBySideOptions) -> StringLines {
+        self.render_side_by_side_colored(options, &Theme::plain())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the old and new lines in two columns like `render_side_by_side`, coloured with a
+    /// theme. The line numbers and the markers are rendered in the gutter style, the changed
+    /// words within changed lines are highlighted and equal lines are rendered in the context
+    /// style.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for rendering.
+    /// * `theme` - the styles of the lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::color::*;
+    /// use rich_lib::diff::*;
+    ///
+    /// let diff = String::from("a b").diff(&String::from("a c"));
+    /// let options = SideBySideOptions::new(13).line_numbers(true);
+    /// let theme = Theme::new();
+    ///
+    /// assert_eq!(
+    ///     diff.render_side_by_side_colored(&options, &Theme::plain()),
+    ///     diff.render_side_by_side(&options)
+    /// );
+    /// assert_eq!(
+    ///     diff.render_side_by_side_colored(&options, &theme)[0],
+    ///     format!(
+    ///         "{} {}{} {} {} {}{}",
+    ///         theme.gutter.paint("1"),
+    ///         theme.deleted.paint("a "),
+    ///         theme.deleted_highlight.paint("b"),
+    ///         theme.gutter.paint("|"),
+    ///         theme.gutter.paint("1"),
+    ///         theme.inserted.paint("a "),
+    ///         theme.inserted_highlight.paint("c")
+    ///     )
+    /// );
+    /// ```
+    pub fn render_side_by_side_colored(
+        &self,
+        options: &SideBySideOptions,
+        theme: &Theme,
+    ) -> StringLines {
+        let number_width = if options.line_numbers {
+            digits(self.old.len().max(self.new.len())) + 1
+        } else {
+            0
+        };
+        let column = options.width.saturating_sub(3) / 2;
+        let text_width = column.saturating_sub(number_width).max(1);
+        let mut result = StringLines::new();
+
+        for row in self.rows() {
+            if row.equal && options.suppress_common {
+                continue;
+            }
+
+            let marker = match (row.old, row.new) {
+                _ if row.equal => ' ',
+                (Some(_), Some(_)) => '|',
+                (Some(_), None) if row.moved => '(',
+                (Some(_), None) => '<',
+                _ if row.moved => ')',
+                _ => '>',
+            };
+            let (old_style, new_style) = if row.equal {
+                (&theme.context, &theme.context)
+            } else if row.moved {
+                (&theme.moved, &theme.moved)
+            } else {
+                (&theme.deleted, &theme.inserted)
+            };
+            let tokens = match (row.old, row.new) {
+                (Some(old), Some(new)) if !row.equal && !theme.is_plain() => {
+                    diff_words(&self.old[old], &self.new[new])
+                }
+                _ => Vec::new(),
+            };
+
+            let left = row.old.map(|index| Side {
+                number: index + 1,
+                cells: cells(
+                    &parts(&self.old[index], &tokens, Tag::Delete),
+                    text_width,
+                    options,
+                ),
+                style: old_style,
+                highlight: &theme.deleted_highlight,
+            });
+            let right = row.new.map(|index| Side {
+                number: index + 1,
+                cells: cells(
+                    &parts(&self.new[index], &tokens, Tag::Insert),
+                    text_width,
+                    options,
+                ),
+                style: new_style,
+                highlight: &theme.inserted_highlight,
+            });
+            let count = left
+                .iter()
+                .chain(right.iter())
+                .map(|side| side.cells.len())
+                .max()
+                .unwrap_or(1);
+
+            for line in 0..count {
+                let mut rendered = String::new();
+
+                rendered.push_str(&render_side(&left, line, number_width, text_width, theme));
+                rendered.push(' ');
+                if line == 0 && marker != ' ' {
+                    rendered.push_str(&theme.gutter.paint(&marker.to_string()));
+                } else {
+                    rendered.push(' ');
+                }
+                rendered.push(' ');
+                rendered.push_str(&render_side(&right, line, number_width, text_width, theme));
+
+                result.push(String::from(rendered.trim_end()));
+            }
+        }
+
+        result
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// A cell is made of runs of text and whether they are highlighted.
+type Cell = Vec<(String, bool)>;
+
+/************************************************************************************************/
+
+/// One side of a row, the line number and the cells of the line with their styles.
+struct Side<'a> {
+    number: usize,
+    cells: Vec<Cell>,
+    style: &'a Style,
+    highlight: &'a Style,
+}
+
+/************************************************************************************************/
+
+/// Returns the parts of one side of a line and whether they are highlighted, using the tokens
+/// of a changed line when there are any.
+fn parts<'a>(line: &'a str, tokens: &'a [Token], side: Tag) -> Vec<(&'a str, bool)> {
+    if tokens.is_empty() {
+        return vec![(line, false)];
+    }
+
+    tokens
+        .iter()
+        .filter(|token| token.tag == Tag::Equal || token.tag == side)
+        .map(|token| (token.text.as_str(), token.tag != Tag::Equal))
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Splits the parts of a line into cells of a given width, tabs are expanded to eight columns.
+fn cells(parts: &[(&str, bool)], width: usize, options: &SideBySideOptions) -> Vec<Cell> {
+    let stops = TabStops::Uniform(8);
+    let mut cells: Vec<Cell> = vec![Vec::new()];
+    let mut cell_width = 0;
+    let mut column = 0;
+
+    for (text, highlight) in parts.iter() {
+        for grapheme in text.graphemes(true) {
+            let expanded = if grapheme == "\t" {
+                let next = stops.next_stop(column).unwrap_or(column + 1);
+                " ".repeat(next - column)
+            } else {
+                String::from(grapheme)
+            };
+            column += display_width(&expanded);
+
+            for grapheme in expanded.graphemes(true) {
+                let grapheme_width = display_width(grapheme);
+                if cell_width + grapheme_width > width {
+                    if options.overflow == Overflow::Truncate {
+                        return cells;
+                    }
+                    cells.push(Vec::new());
+                    cell_width = 0;
+                }
+
+                let cell = cells.last_mut().expect("a cell");
+                match cell.last_mut() {
+                    Some((run, run_highlight)) if run_highlight == highlight => {
+                        run.push_str(grapheme)
+                    }
+                    _ => cell.push((String::from(grapheme), *highlight)),
+                }
+                cell_width += grapheme_width;
+            }
+        }
+    }
+
+    cells
+}
+
+/************************************************************************************************/
+
+/// Renders a single line of one side of a row, padded to the width of the column.
+fn render_side(
+    side: &Option<Side>,
+    line: usize,
+    number_width: usize,
+    text_width: usize,
+    theme: &Theme,
+) -> String {
+    let number = match side {
+        Some(side) if line == 0 && number_width > 0 => format!(
+            "{} ",
+            theme.gutter.paint(&format!(
+                "{:>width$}",
+                side.number,
+                width = number_width - 1
+            ))
+        ),
+        _ => " ".repeat(number_width),
+    };
+    let (cell, width) = match side
+        .as_ref()
+        .and_then(|side| side.cells.get(line).map(|cell| (side, cell)))
+    {
+        Some((side, cell)) => (
+            cell.iter()
+                .map(|(text, highlight)| {
+                    if *highlight {
+                        side.highlight.paint(text)
+                    } else {
+                        side.style.paint(text)
+                    }
+                })
+                .collect::<String>(),
+            cell.iter().map(|(text, _)| display_width(text)).sum(),
+        ),
+        None => (String::new(), 0),
+    };
+    let padding = text_width.saturating_sub(width);
+
+    format!("{}{}{}", number, cell, " ".repeat(padding))
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,489 @@
+//! `Fields` is a module that contains traits to split lines into fields, like `awk`, and to
+//! select fields or characters of every line, like `cut -f` and `cut -c`.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+#[cfg(feature = "regex")]
+use regex::Regex;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The separator between the fields of a line.
+#[derive(Debug, Clone)]
+pub enum Delimiter {
+    /// Runs of whitespace, leading and trailing whitespace is ignored like in `awk`.
+    Whitespace,
+    /// A single character, every occurrence separates two fields like in `cut`.
+    Char(char),
+    /// A string of one or more characters.
+    Str(String),
+    /// The matches of a regular expression, empty matches are ignored.
+    #[cfg(feature = "regex")]
+    Regex(Regex),
+}
+
+/************************************************************************************************/
+
+/// An iterator over the fields of a single line.
+#[derive(Debug, Clone)]
+pub struct Fields<'a, 'b> {
+    rest: Option<&'a str>,
+    delimiter: &'b Delimiter,
+}
+
+/************************************************************************************************/
+
+/// A list of (one based) field or character numbers, like `1,3-5,7-`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Selection {
+    /// The ranges of numbers, an open end is `usize::MAX`.
+    pub ranges: Vec<RangeInclusive<usize>>,
+}
+
+/************************************************************************************************/
+
+/// The options used when selecting fields.
+#[derive(Debug, Clone)]
+pub struct FieldOptions {
+    /// The separator between the fields of a line.
+    pub delimiter: Delimiter,
+    /// The separator between the selected fields. When `None` a single character or string
+    /// delimiter is kept and a space is used otherwise.
+    pub output_delimiter: Option<String>,
+    /// Drops the lines without a delimiter when `true`, they are kept unchanged otherwise.
+    pub only_delimited: bool,
+}
+
+/************************************************************************************************/
+
+/// The error returned when parsing a selection fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectionError {
+    /// The (one based) column of the problem.
+    pub column: usize,
+    /// The description of the problem.
+    pub message: String,
+}
+
+/************************************************************************************************/
+
+/// The `FieldLines` trait contains all functions to be implemented that split lines into
+/// fields and select fields or characters.
+pub trait FieldLines {
+    /// Returns a single field of every line, lines without that field return an empty string.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the (zero based) index of the field.
+    /// * `delimiter` - the separator between the fields.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::fields::*;
+    ///
+    /// let s = String::from("  a  b c\nd e\nf");
+    ///
+    /// assert_eq!(s.column(1, &Delimiter::Whitespace), vec!["b", "e", ""]);
+    /// ```
+    fn column(&self, index: usize, delimiter: &Delimiter) -> StringLines;
+
+    /// Keeps the selected fields of every line, like `cut -f`. Fields are kept in their order
+    /// in the line, whatever the order of the selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `selection` - the (one based) numbers of the fields.
+    /// * `options` - the delimiters used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::fields::*;
+    ///
+    /// let s = String::from("a:b:c:d:e\nno fields");
+    /// let selection: Selection = "4-,1".parse().unwrap();
+    /// let options = FieldOptions::new(Delimiter::Char(':')).output_delimiter(Some("|"));
+    ///
+    /// assert_eq!(s.cut_fields(&selection, &options), "a|d|e\nno fields");
+    /// ```
+    fn cut_fields(self, selection: &Selection, options: &FieldOptions) -> Self;
+
+    /// Keeps the selected characters of every line, like `cut -c`.
+    ///
+    /// # Arguments
+    ///
+    /// * `selection` - the (one based) numbers of the characters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::fields::*;
+    ///
+    /// let s = String::from("abcdef\nxyz");
+    ///
+    /// assert_eq!(s.cut_chars(&"-2,5-".parse().unwrap()), "abef\nxy");
+    /// ```
+    fn cut_chars(self, selection: &Selection) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns an iterator over the fields of a single line.
+///
+/// # Arguments
+///
+/// * `line` - the line.
+/// * `delimiter` - the separator between the fields.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::fields::*;
+///
+/// let delimiter = Delimiter::Str(String::from(", "));
+///
+/// assert_eq!(fields("a, b, c", &delimiter).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+/// assert_eq!(fields("a::b", &Delimiter::Char(':')).nth(1), Some(""));
+/// ```
+pub fn fields<'a, 'b>(line: &'a str, delimiter: &'b Delimiter) -> Fields<'a, 'b> {
+    Fields {
+        rest: Some(line),
+        delimiter,
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Delimiter {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a delimiter that matches a regular expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - the regular expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::fields::*;
+    ///
+    /// let delimiter = Delimiter::regex(r"\s*,\s*").unwrap();
+    ///
+    /// assert_eq!(fields("a , b,c", &delimiter).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Delimiter::Regex(Regex::new(pattern)?))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the byte range of the first delimiter in a text.
+    fn find(&self, text: &str) -> Option<(usize, usize)> {
+        match self {
+            Delimiter::Whitespace => {
+                let start = text.find(char::is_whitespace)?;
+                let end = text[start..]
+                    .find(|c: char| !c.is_whitespace())
+                    .map_or(text.len(), |end| start + end);
+                Some((start, end))
+            }
+            Delimiter::Char(c) => text.find(*c).map(|start| (start, start + c.len_utf8())),
+            Delimiter::Str(s) if s.is_empty() => None,
+            Delimiter::Str(s) => text.find(s.as_str()).map(|start| (start, start + s.len())),
+            #[cfg(feature = "regex")]
+            Delimiter::Regex(regex) => regex
+                .find_iter(text)
+                .find(|m| !m.as_str().is_empty())
+                .map(|m| (m.start(), m.end())),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl<'a> Iterator for Fields<'a, '_> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut rest = self.rest?;
+
+        if let Delimiter::Whitespace = self.delimiter {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                self.rest = None;
+                return None;
+            }
+        }
+
+        match self.delimiter.find(rest) {
+            Some((start, end)) => {
+                self.rest = Some(&rest[end..]);
+                Some(&rest[..start])
+            }
+            None => {
+                self.rest = None;
+                Some(rest)
+            }
+        }
+    }
+}
+
+/************************************************************************************************/
+
+impl Selection {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates an empty selection.
+    pub fn new() -> Self {
+        Selection { ranges: Vec::new() }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Adds a range of (one based) numbers.
+    pub fn range(mut self, range: RangeInclusive<usize>) -> Self {
+        self.ranges.push(range);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when a (one based) number is selected.
+    pub fn contains(&self, number: usize) -> bool {
+        self.ranges.iter().any(|range| range.contains(&number))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl FromStr for Selection {
+    type Err = SelectionError;
+
+    /// Parses a comma separated list of numbers (`3`), closed ranges (`3-5`) and ranges that
+    /// are open at the start (`-3`) or the end (`3-`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut selection = Selection::new();
+        let mut column = 1;
+
+        for item in s.split(',') {
+            let number = |text: &str, offset: usize| match text.parse::<usize>() {
+                Ok(number) if number > 0 => Ok(number),
+                Ok(_) => Err(SelectionError::new(offset, "numbers start at 1")),
+                Err(_) => Err(SelectionError::new(offset, "invalid number")),
+            };
+            let range = match item.find('-') {
+                _ if item.is_empty() => return Err(SelectionError::new(column, "empty item")),
+                Some(_) if item == "-" => return Err(SelectionError::new(column, "empty range")),
+                Some(dash) => {
+                    let start = match &item[..dash] {
+                        "" => 1,
+                        text => number(text, column)?,
+                    };
+                    let end = match &item[dash + 1..] {
+                        "" => usize::MAX,
+                        text => number(text, column + dash + 1)?,
+                    };
+                    if end < start {
+                        return Err(SelectionError::new(column, "decreasing range"));
+                    }
+                    start..=end
+                }
+                None => {
+                    let number = number(item, column)?;
+                    number..=number
+                }
+            };
+
+            selection.ranges.push(range);
+            column += item.chars().count() + 1;
+        }
+
+        Ok(selection)
+    }
+}
+
+/************************************************************************************************/
+
+impl FieldOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options for a delimiter, which keep the lines without a delimiter.
+    ///
+    /// # Arguments
+    ///
+    /// * `delimiter` - the separator between the fields.
+    pub fn new(delimiter: Delimiter) -> Self {
+        FieldOptions {
+            delimiter,
+            output_delimiter: None,
+            only_delimited: false,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the separator between the selected fields.
+    pub fn output_delimiter(mut self, output_delimiter: Option<&str>) -> Self {
+        self.output_delimiter = output_delimiter.map(String::from);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether the lines without a delimiter are dropped.
+    pub fn only_delimited(mut self, only_delimited: bool) -> Self {
+        self.only_delimited = only_delimited;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the separator between the selected fields.
+    fn joiner(&self) -> String {
+        match (&self.output_delimiter, &self.delimiter) {
+            (Some(output_delimiter), _) => output_delimiter.clone(),
+            (None, Delimiter::Char(c)) => c.to_string(),
+            (None, Delimiter::Str(s)) => s.clone(),
+            (None, _) => String::from(" "),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for FieldOptions {
+    fn default() -> Self {
+        FieldOptions::new(Delimiter::Whitespace)
+    }
+}
+
+/************************************************************************************************/
+
+impl SelectionError {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(column: usize, message: &str) -> Self {
+        SelectionError {
+            column,
+            message: String::from(message),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+/************************************************************************************************/
+
+impl std::error::Error for SelectionError {}
+
+/************************************************************************************************/
+
+impl FieldLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn column(&self, index: usize, delimiter: &Delimiter) -> StringLines {
+        self.iter()
+            .map(|line| String::from(fields(line, delimiter).nth(index).unwrap_or("")))
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn cut_fields(self, selection: &Selection, options: &FieldOptions) -> Self {
+        let joiner = options.joiner();
+
+        self.into_iter()
+            .filter_map(|line| {
+                if options.delimiter.find(&line).is_none() {
+                    return if options.only_delimited {
+                        None
+                    } else {
+                        Some(line)
+                    };
+                }
+
+                let selected: Vec<&str> = fields(&line, &options.delimiter)
+                    .enumerate()
+                    .filter(|(index, _)| selection.contains(index + 1))
+                    .map(|(_, field)| field)
+                    .collect();
+                Some(selected.join(&joiner))
+            })
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn cut_chars(self, selection: &Selection) -> Self {
+        self.into_iter()
+            .map(|line| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(index, _)| selection.contains(index + 1))
+                    .map(|(_, c)| c)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl FieldLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn column(&self, index: usize, delimiter: &Delimiter) -> StringLines {
+        self.lines()
+            .map(|line| String::from(fields(line, delimiter).nth(index).unwrap_or("")))
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn cut_fields(self, selection: &Selection, options: &FieldOptions) -> Self {
+        self.split().cut_fields(selection, options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn cut_chars(self, selection: &Selection) -> Self {
+        self.split().cut_chars(selection).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
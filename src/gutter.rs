@@ -0,0 +1,197 @@
+//! `Gutter` is a module that contains traits to render lines with line numbers in a gutter.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The options used when rendering lines with line numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GutterOptions {
+    /// The number of the first line, `1` by default.
+    pub start: usize,
+    /// The separator between the gutter and the line, `" | "` by default.
+    pub separator: String,
+    /// Blank lines are not numbered (like `nl -b t`) when `true`.
+    pub skip_blank: bool,
+    /// Renders only the lines around a target line when set. The first value is the index of
+    /// the target line and the second value the amount of lines to render before and after it.
+    pub window: Option<(usize, usize)>,
+}
+
+/************************************************************************************************/
+
+/// The `NumberLines` trait contains all functions to be implemented that render lines with line
+/// numbers.
+pub trait NumberLines {
+    /// Prefixes the lines with right-aligned line numbers. The width of the gutter is sized to
+    /// the largest rendered line number. Blank lines have their trailing whitespace removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for rendering.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::gutter::*;
+    ///
+    /// let s = String::from("line0\n\nline2").number_lines(&GutterOptions::new().start(9));
+    ///
+    /// assert_eq!(s, " 9 | line0\n10 |\n11 | line2");
+    ///
+    /// let s = String::from("line0\n\nline2").number_lines(&GutterOptions::new().skip_blank(true));
+    ///
+    /// assert_eq!(s, "1 | line0\n  |\n2 | line2");
+    /// ```
+    fn number_lines(self, options: &GutterOptions) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl GutterOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options.
+    pub fn new() -> Self {
+        GutterOptions {
+            start: 1,
+            separator: String::from(" | "),
+            skip_blank: false,
+            window: None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the number of the first line.
+    pub fn start(mut self, start: usize) -> Self {
+        self.start = start;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the separator between the gutter and the line.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = String::from(separator);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether blank lines are numbered.
+    pub fn skip_blank(mut self, skip_blank: bool) -> Self {
+        self.skip_blank = skip_blank;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders only the lines around a target line.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the index of the target line.
+    /// * `context` - the amount of lines to render before and after the target line.
+    pub fn window(mut self, index: usize, context: usize) -> Self {
+        self.window = Some((index, context));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for GutterOptions {
+    fn default() -> Self {
+        GutterOptions::new()
+    }
+}
+
+/************************************************************************************************/
+
+impl NumberLines for StringLines {
+    fn number_lines(self, options: &GutterOptions) -> Self {
+        let mut number = options.start;
+        let mut numbered = Vec::with_capacity(self.len());
+
+        for line in self.iter() {
+            if options.skip_blank && line.trim().is_empty() {
+                numbered.push(None);
+            } else {
+                numbered.push(Some(number));
+                number += 1;
+            }
+        }
+
+        let range = match options.window {
+            Some((index, context)) => {
+                let end = self
+                    .len()
+                    .min(index.saturating_add(context.saturating_add(1)));
+                index.saturating_sub(context).min(end)..end
+            }
+            None => 0..self.len(),
+        };
+        let width = numbered[range.clone()]
+            .iter()
+            .flatten()
+            .map(|number| digits(*number))
+            .max()
+            .unwrap_or(1);
+
+        range
+            .map(|index| render_gutter(numbered[index], width, &options.separator, &self[index]))
+            .collect()
+    }
+}
+
+/************************************************************************************************/
+
+impl NumberLines for String {
+    fn number_lines(self, options: &GutterOptions) -> Self {
+        self.split().number_lines(options).merge()
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns the amount of decimal digits of a number.
+pub(crate) fn digits(number: usize) -> usize {
+    number.to_string().len()
+}
+
+/************************************************************************************************/
+
+/// Renders a single line with a gutter of the given width. Lines without content have their
+/// trailing whitespace removed, with or without a number.
+pub(crate) fn render_gutter(
+    number: Option<usize>,
+    width: usize,
+    separator: &str,
+    line: &str,
+) -> String {
+    let number = number.map_or_else(String::new, |number| number.to_string());
+    let rendered = format!("{:>width$}{}{}", number, separator, line, width = width);
+
+    if line.trim().is_empty() {
+        String::from(rendered.trim_end())
+    } else {
+        rendered
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -1,10 +1,34 @@
 //! `rich_lib` is a library with various extension and helper functionality.
+//!
+//! The `address` and `sed` modules and the regular expression delimiter of the `fields` module
+//! need the `regex` feature, which is enabled by default.
 
 /************************************************************************************************/
 /************************************************************************************************/
 /************************************************************************************************/
 
+#[cfg(feature = "regex")]
+pub mod address;
+pub mod align;
+pub mod ansi;
+pub mod blame;
+pub mod color;
+pub mod conflicts;
+pub mod diagnostics;
+pub mod diff;
+pub mod fields;
+pub mod gutter;
 pub mod lines;
+pub mod logical;
+pub mod paragraphs;
+#[cfg(feature = "regex")]
+pub mod sed;
+pub mod sloc;
+pub mod table;
+pub mod tabs;
+pub mod whitespace;
+pub mod width;
+pub mod wrap;
 
 #[cfg(test)]
 mod tests;
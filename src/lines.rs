@@ -341,8 +341,8 @@ impl MergeLines for StringLines {
     fn merge(&self) -> String {
         let mut buffer = String::new();
 
-        for line in self.iter() {
-            if !buffer.is_empty() {
+        for (index, line) in self.iter().enumerate() {
+            if index > 0 {
                 buffer.push('\n');
             }
             buffer.push_str(line);
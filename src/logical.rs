@@ -0,0 +1,345 @@
+//! `Logical` is a module that contains traits to group physical lines into logical lines, like
+//! lines continued with a trailing backslash or folded header lines.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use std::ops::Range;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The options used when grouping lines into logical lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContinuationOptions {
+    /// The marker at the end of a line that continues it on the next line, a backslash by
+    /// default. The marker is removed from the joined text. A marker that is escaped by itself,
+    /// like `\\`, does not continue the line.
+    pub marker: Option<String>,
+    /// Lines that start with whitespace continue the previous line when `true`, like folded
+    /// headers in RFC 822 messages. Blank lines never continue a line.
+    pub folding: bool,
+    /// The text put between the joined lines.
+    pub separator: String,
+}
+
+/************************************************************************************************/
+
+/// A logical line, one or more physical lines joined together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogicalLine {
+    /// The joined text.
+    pub text: String,
+    /// The indexes of the physical lines.
+    pub range: Range<usize>,
+    /// The byte offsets in the joined text where the physical lines start.
+    pub starts: Vec<usize>,
+}
+
+/************************************************************************************************/
+
+/// An iterator over the logical lines of a set of physical lines.
+#[derive(Debug, Clone)]
+pub struct LogicalLines<'a, 'b> {
+    lines: &'a [String],
+    options: &'b ContinuationOptions,
+    index: usize,
+}
+
+/************************************************************************************************/
+
+/// The `ContinuationLines` trait contains all functions to be implemented that group lines into
+/// logical lines.
+pub trait ContinuationLines {
+    /// Returns the logical lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for grouping.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::logical::*;
+    ///
+    /// let s = String::from("CFLAGS = -O2 \\\n    -Wall\nLDFLAGS =");
+    /// let logical = s.logical_lines(&ContinuationOptions::new());
+    ///
+    /// assert_eq!(logical[0].text, "CFLAGS = -O2     -Wall");
+    /// assert_eq!(logical[0].range, 0..2);
+    /// assert_eq!(logical[1].range, 2..3);
+    /// ```
+    fn logical_lines(&self, options: &ContinuationOptions) -> Vec<LogicalLine>;
+
+    /// Joins the continued lines, every logical line becomes a single line.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for grouping.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::logical::*;
+    ///
+    /// let s = String::from("Subject: a long\n  subject\nTo: you")
+    ///             .join_continuations(&ContinuationOptions::headers());
+    ///
+    /// assert_eq!(s, "Subject: a long  subject\nTo: you");
+    /// ```
+    fn join_continuations(self, options: &ContinuationOptions) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns an iterator over the logical lines of a set of physical lines.
+///
+/// # Arguments
+///
+/// * `lines` - the physical lines.
+/// * `options` - the options used for grouping.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::logical::*;
+///
+/// let lines = vec![String::from("echo a \\"), String::from("b"), String::from("echo \\\\")];
+/// let options = ContinuationOptions::new();
+/// let texts: Vec<String> = logical_lines(&lines, &options).map(|line| line.text).collect();
+///
+/// assert_eq!(texts, vec!["echo a b", "echo \\\\"]);
+/// ```
+pub fn logical_lines<'a, 'b>(
+    lines: &'a [String],
+    options: &'b ContinuationOptions,
+) -> LogicalLines<'a, 'b> {
+    LogicalLines {
+        lines,
+        options,
+        index: 0,
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl ContinuationOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options, lines are continued with a trailing backslash.
+    pub fn new() -> Self {
+        ContinuationOptions {
+            marker: Some(String::from("\\")),
+            folding: false,
+            separator: String::new(),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the options for folded headers, lines that start with whitespace continue the
+    /// previous line and there is no marker.
+    pub fn headers() -> Self {
+        ContinuationOptions::new().no_marker().folding(true)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the marker at the end of a line that continues it on the next line.
+    pub fn marker(mut self, marker: &str) -> Self {
+        self.marker = Some(String::from(marker));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Removes the marker, lines are not continued by a trailing marker.
+    pub fn no_marker(mut self) -> Self {
+        self.marker = None;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether lines that start with whitespace continue the previous line.
+    pub fn folding(mut self, folding: bool) -> Self {
+        self.folding = folding;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the text put between the joined lines.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = String::from(separator);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the line without a continuation marker, and whether the line is continued.
+    fn strip_marker<'a>(&self, line: &'a str) -> (&'a str, bool) {
+        let marker = match &self.marker {
+            Some(marker) if !marker.is_empty() => marker.as_str(),
+            _ => return (line, false),
+        };
+
+        let mut rest = line;
+        let mut count = 0;
+        while let Some(stripped) = rest.strip_suffix(marker) {
+            rest = stripped;
+            count += 1;
+        }
+
+        if count % 2 == 1 {
+            (&line[..line.len() - marker.len()], true)
+        } else {
+            (line, false)
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when a line is folded into the previous line.
+    fn is_folded(&self, line: &str) -> bool {
+        self.folding && line.starts_with(char::is_whitespace) && !line.trim().is_empty()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for ContinuationOptions {
+    fn default() -> Self {
+        ContinuationOptions::new()
+    }
+}
+
+/************************************************************************************************/
+
+impl LogicalLine {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the index of the physical line and the byte column within that line of a byte
+    /// offset in the joined text. Offsets within a separator or a removed marker point to the
+    /// end of the preceding physical line.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - the byte offset in the joined text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::logical::*;
+    ///
+    /// let s = String::from("a = 1 + \\\n  2");
+    /// let line = &s.logical_lines(&ContinuationOptions::new())[0];
+    ///
+    /// assert_eq!(line.position(4), (0, 4));
+    /// assert_eq!(line.position(10), (1, 2));
+    /// ```
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let part = self
+            .starts
+            .iter()
+            .rposition(|&start| start <= offset)
+            .unwrap_or(0);
+        let start = self.starts.get(part).copied().unwrap_or(0);
+
+        (self.range.start + part, offset - start.min(offset))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl<'a, 'b> Iterator for LogicalLines<'a, 'b> {
+    type Item = LogicalLine;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.index;
+        let mut text = String::new();
+        let mut starts = Vec::new();
+
+        while let Some(line) = self.lines.get(self.index) {
+            if !starts.is_empty() {
+                text.push_str(&self.options.separator);
+            }
+            starts.push(text.len());
+
+            let (content, continued) = self.options.strip_marker(line);
+            text.push_str(content);
+            self.index += 1;
+
+            let folded = self
+                .lines
+                .get(self.index)
+                .is_some_and(|next| self.options.is_folded(next));
+            if !continued && !folded {
+                break;
+            }
+        }
+
+        if starts.is_empty() {
+            None
+        } else {
+            Some(LogicalLine {
+                text,
+                range: first..self.index,
+                starts,
+            })
+        }
+    }
+}
+
+/************************************************************************************************/
+
+impl ContinuationLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn logical_lines(&self, options: &ContinuationOptions) -> Vec<LogicalLine> {
+        logical_lines(self, options).collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn join_continuations(self, options: &ContinuationOptions) -> Self {
+        logical_lines(&self, options)
+            .map(|line| line.text)
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl ContinuationLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn logical_lines(&self, options: &ContinuationOptions) -> Vec<LogicalLine> {
+        self.split().logical_lines(options)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn join_continuations(self, options: &ContinuationOptions) -> Self {
+        self.split().join_continuations(options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,325 @@
+//! `Paragraphs` is a module that contains traits to split lines into paragraphs, to work on
+//! every paragraph separately and to merge them back together.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use crate::wrap::*;
+use std::ops::Range;
+use std::slice::Iter;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// A paragraph, a run of consecutive non-blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paragraph {
+    /// The indexes of the lines of the paragraph.
+    pub range: Range<usize>,
+    /// The lines of the paragraph.
+    pub lines: StringLines,
+}
+
+/************************************************************************************************/
+
+/// Lines split into paragraphs. The blank or whitespace-only lines separating the paragraphs are
+/// kept as they are, so merging unchanged paragraphs results in the original text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paragraphs {
+    /// The paragraphs in order.
+    pub paragraphs: Vec<Paragraph>,
+    /// The separating lines. The separator at index `i` precedes the paragraph at index `i`, the
+    /// last separator follows the last paragraph, so there is always one more separator than
+    /// there are paragraphs. The first and last separator are empty when the text starts or ends
+    /// with a paragraph.
+    pub separators: Vec<StringLines>,
+    /// Ends the merged text with a newline when `true`.
+    pub final_newline: bool,
+}
+
+/************************************************************************************************/
+
+/// The `ParagraphLines` trait contains all functions to be implemented that split an object into
+/// paragraphs.
+pub trait ParagraphLines {
+    /// Splits into paragraphs separated by one or more blank or whitespace-only lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::paragraphs::*;
+    ///
+    /// let p = String::from("one\ntwo\n\n  \nthree\n").paragraphs();
+    ///
+    /// assert_eq!(p.len(), 2);
+    /// assert_eq!(p.paragraphs[0].range, 0..2);
+    /// assert_eq!(p.paragraphs[1].lines, vec!["three"]);
+    /// assert_eq!(p.separators[1], vec!["", "  "]);
+    /// ```
+    fn paragraphs(&self) -> Paragraphs;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Paragraph {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the lines of the paragraph joined by newlines.
+    pub fn text(&self) -> String {
+        self.lines.merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Paragraphs {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Splits lines into paragraphs separated by one or more blank or whitespace-only lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - the lines to split.
+    pub fn new(lines: &[String]) -> Self {
+        let mut paragraphs = Vec::new();
+        let mut separators = vec![StringLines::new()];
+
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                if separators.len() == paragraphs.len() {
+                    separators.push(StringLines::new());
+                }
+                separators[paragraphs.len()].push(line.clone());
+            } else if separators.len() > paragraphs.len() {
+                paragraphs.push(Paragraph {
+                    range: index..index + 1,
+                    lines: vec![line.clone()],
+                });
+            } else {
+                let paragraph = paragraphs.last_mut().unwrap();
+                paragraph.range.end = index + 1;
+                paragraph.lines.push(line.clone());
+            }
+        }
+
+        if separators.len() == paragraphs.len() {
+            separators.push(StringLines::new());
+        }
+
+        Paragraphs {
+            paragraphs,
+            separators,
+            final_newline: false,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the amount of paragraphs.
+    pub fn len(&self) -> usize {
+        self.paragraphs.len()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when there are no paragraphs.
+    pub fn is_empty(&self) -> bool {
+        self.paragraphs.is_empty()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns an iterator over the paragraphs.
+    pub fn iter(&self) -> Iter<'_, Paragraph> {
+        self.paragraphs.iter()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Replaces the lines of every paragraph with the lines returned by a function. The ranges
+    /// of the paragraphs are updated to the new positions of the lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - returns the new lines of a paragraph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::lines::*;
+    /// use rich_lib::paragraphs::*;
+    ///
+    /// let p = String::from("a\nb\n\nc")
+    ///     .paragraphs()
+    ///     .map(|paragraph| vec![paragraph.lines.join(" ")]);
+    ///
+    /// assert_eq!(p.merge(), "a b\n\nc");
+    /// assert_eq!(p.paragraphs[1].range, 2..3);
+    /// ```
+    pub fn map<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&Paragraph) -> StringLines,
+    {
+        for paragraph in self.paragraphs.iter_mut() {
+            paragraph.lines = f(paragraph);
+        }
+        self.renumber();
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Keeps only the paragraphs for which a predicate returns `true`. A removed paragraph is
+    /// removed together with the separator following it, or with the separator preceding it when
+    /// it is the last paragraph. The ranges of the paragraphs are updated to the new positions of
+    /// the lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - returns `true` for the paragraphs to keep.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::lines::*;
+    /// use rich_lib::paragraphs::*;
+    ///
+    /// let p = String::from("keep\n\nTODO: drop\n\n\nkeep too")
+    ///     .paragraphs()
+    ///     .filter(|paragraph| !paragraph.text().starts_with("TODO"));
+    ///
+    /// assert_eq!(p.merge(), "keep\n\nkeep too");
+    /// ```
+    pub fn filter<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&Paragraph) -> bool,
+    {
+        let mut index = 0;
+
+        while index < self.paragraphs.len() {
+            if f(&self.paragraphs[index]) {
+                index += 1;
+                continue;
+            }
+
+            self.paragraphs.remove(index);
+            if index == self.paragraphs.len() {
+                self.separators.remove(index);
+            } else {
+                self.separators.remove(index + 1);
+            }
+        }
+
+        self.renumber();
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Reflows every paragraph to the width given in the options. The separators are kept as
+    /// they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for reflowing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::lines::*;
+    /// use rich_lib::paragraphs::*;
+    /// use rich_lib::wrap::*;
+    ///
+    /// let p = String::from("the quick\nbrown fox\n \njumps").paragraphs();
+    ///
+    /// assert_eq!(p.reflow(&WrapOptions::new(15)).merge(), "the quick brown\nfox\n \njumps");
+    /// ```
+    pub fn reflow(self, options: &WrapOptions) -> Self {
+        self.map(|paragraph| wrap_paragraph(&paragraph.lines.join(" "), options))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns all lines, the paragraphs and their separators in order.
+    pub fn lines(&self) -> StringLines {
+        let mut result = StringLines::new();
+
+        for (separator, paragraph) in self.separators.iter().zip(self.paragraphs.iter()) {
+            result.extend(separator.iter().cloned());
+            result.extend(paragraph.lines.iter().cloned());
+        }
+        if let Some(separator) = self.separators.last() {
+            result.extend(separator.iter().cloned());
+        }
+
+        result
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn renumber(&mut self) {
+        let mut index = 0;
+
+        for (separator, paragraph) in self.separators.iter().zip(self.paragraphs.iter_mut()) {
+            index += separator.len();
+            paragraph.range = index..index + paragraph.lines.len();
+            index = paragraph.range.end;
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl<'a> IntoIterator for &'a Paragraphs {
+    type Item = &'a Paragraph;
+    type IntoIter = Iter<'a, Paragraph>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.paragraphs.iter()
+    }
+}
+
+/************************************************************************************************/
+
+impl MergeLines for Paragraphs {
+    fn merge(&self) -> String {
+        let mut buffer = self.lines().merge();
+
+        if self.final_newline {
+            buffer.push('\n');
+        }
+
+        buffer
+    }
+}
+
+/************************************************************************************************/
+
+impl ParagraphLines for StringLines {
+    fn paragraphs(&self) -> Paragraphs {
+        Paragraphs::new(self)
+    }
+}
+
+/************************************************************************************************/
+
+impl ParagraphLines for String {
+    fn paragraphs(&self) -> Paragraphs {
+        let mut paragraphs = Paragraphs::new(&self.split());
+        paragraphs.final_newline = self.ends_with('\n');
+        paragraphs
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
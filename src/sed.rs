@@ -0,0 +1,984 @@
+//! `Sed` is a module that runs small `sed` like editing scripts over lines, in memory or over a
+//! stream. Patterns are regular expressions in the syntax of the `regex` crate.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::address::Parser;
+use crate::lines::*;
+use regex::{Captures, Regex, RegexBuilder};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::iter::Peekable;
+use std::str::FromStr;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// A condition that selects a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Match {
+    /// The line with the given (one based) number.
+    Line(usize),
+    /// The last line, `$`.
+    Last,
+    /// The lines that match a regular expression, `/pattern/` or `\cpatternc`. An empty pattern
+    /// repeats the last used pattern.
+    Pattern(String),
+}
+
+/************************************************************************************************/
+
+/// The lines a command is executed on.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Selector {
+    /// The first line, all lines are selected when `None`.
+    pub first: Option<Match>,
+    /// The last line of a range starting at the first line, if any.
+    pub last: Option<Match>,
+    /// Selects the lines that do not match when `true`, `!`.
+    pub negated: bool,
+    /// The (zero based) index of the range among the ranges of the script, given by
+    /// `Script::parse`. Ranges with the same index share their state.
+    pub range: usize,
+}
+
+/************************************************************************************************/
+
+/// The arguments of the `s` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Substitute {
+    /// The regular expression.
+    pub pattern: String,
+    /// The replacement, `&` is the match and `\1` to `\9` are the groups.
+    pub replacement: String,
+    /// Replaces all matches when `true`, `g`.
+    pub global: bool,
+    /// The (one based) match that is replaced first, `1` by default.
+    pub occurrence: usize,
+    /// Prints the pattern space after a replacement when `true`, `p`.
+    pub print: bool,
+    /// Matches case insensitive when `true`, `i` or `I`.
+    pub ignore_case: bool,
+}
+
+/************************************************************************************************/
+
+/// The kind of a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandKind {
+    /// `{ ... }` runs a block of commands.
+    Block(Vec<Command>),
+    /// `s/pattern/replacement/flags` replaces a match in the pattern space.
+    Substitute(Substitute),
+    /// `y/abc/xyz/` replaces every character of the first list by the character at the same
+    /// position in the second list.
+    Transliterate(Vec<(char, char)>),
+    /// `d` deletes the pattern space and starts the next cycle.
+    Delete,
+    /// `p` prints the pattern space.
+    Print,
+    /// `a text` prints the text at the end of the cycle.
+    Append(String),
+    /// `i text` prints the text.
+    Insert(String),
+    /// `c text` deletes the pattern space and prints the text, for a range at its end.
+    Change(String),
+    /// `h` copies the pattern space to the hold space.
+    Hold,
+    /// `H` appends a newline and the pattern space to the hold space.
+    HoldAppend,
+    /// `g` copies the hold space to the pattern space.
+    Get,
+    /// `G` appends a newline and the hold space to the pattern space.
+    GetAppend,
+    /// `x` exchanges the pattern space and the hold space.
+    Exchange,
+    /// `n` prints the pattern space and replaces it with the next line.
+    Next,
+    /// `N` appends a newline and the next line to the pattern space.
+    NextAppend,
+    /// `=` prints the line number.
+    LineNumber,
+    /// `q` ends the script after the current cycle.
+    Quit,
+}
+
+/************************************************************************************************/
+
+/// A command of a script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    /// The lines the command is executed on.
+    pub selector: Selector,
+    /// The kind of command.
+    pub kind: CommandKind,
+    /// The (one based) line of the command in the script.
+    pub line: usize,
+    /// The (one based) column of the command in the script.
+    pub column: usize,
+}
+
+/************************************************************************************************/
+
+/// A parsed script.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Script {
+    /// The commands.
+    pub commands: Vec<Command>,
+    /// Only prints the pattern space when asked for when `true`, like `sed -n` or a script that
+    /// starts with `#n`.
+    pub quiet: bool,
+}
+
+/************************************************************************************************/
+
+/// The error returned when parsing a script fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SedError {
+    /// The (one based) line in the script.
+    pub line: usize,
+    /// The (one based) column in the script.
+    pub column: usize,
+    /// The description of the problem.
+    pub message: String,
+}
+
+/************************************************************************************************/
+
+/// The `SedLines` trait contains all functions to be implemented that run a script over lines.
+pub trait SedLines {
+    /// Runs a script over the lines and returns the printed lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `script` - the script.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::sed::*;
+    ///
+    /// let script = Script::parse("2d\ns/(\\w+)=(\\w+)/\\2=\\1/\n$a end").unwrap();
+    /// let s = String::from("a=1\nb=2\nc=3").sed(&script);
+    ///
+    /// assert_eq!(s, "1=a\n3=c\nend");
+    /// ```
+    fn sed(self, script: &Script) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Script {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Parses a script. Commands are separated by newlines or `;`, the text of `a`, `i` and `c`
+    /// runs to the end of the line and continues on the next line after a trailing `\`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - the script.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::sed::*;
+    ///
+    /// let error = Script::parse("1,/x/ {\n  p\n  k\n}").unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "line 3, column 3: unknown command 'k'");
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, SedError> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            position: 0,
+        };
+
+        let mut commands = parser.commands(false)?;
+        number_ranges(&mut commands, &mut 0);
+
+        Ok(Script {
+            quiet: text.starts_with("#n\n") || text == "#n",
+            commands,
+        })
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether the pattern space is only printed when asked for.
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Runs the script over the lines read from a stream and writes the printed lines to another
+    /// stream. Lines are read one at a time, as the script asks for them.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - the stream the lines are read from.
+    /// * `output` - the stream the printed lines are written to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::sed::*;
+    ///
+    /// let script = Script::parse("N;s/\\n/+/").unwrap();
+    /// let mut output = Vec::new();
+    /// script.run_stream("a\nb\nc\n".as_bytes(), &mut output).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(output).unwrap(), "a+b\nc\n");
+    /// ```
+    pub fn run_stream<R: BufRead, W: Write>(&self, input: R, mut output: W) -> io::Result<()> {
+        Execution::new(self, input.lines()).run(&mut |text: &str| writeln!(output, "{}", text))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl FromStr for Script {
+    type Err = SedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Script::parse(s)
+    }
+}
+
+/************************************************************************************************/
+
+impl fmt::Display for SedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/************************************************************************************************/
+
+impl std::error::Error for SedError {}
+
+/************************************************************************************************/
+
+impl SedLines for StringLines {
+    fn sed(self, script: &Script) -> Self {
+        let mut result = StringLines::new();
+        let input = self.into_iter().map(Ok);
+        let run = Execution::new(script, input).run(&mut |text: &str| {
+            result.extend(text.split('\n').map(String::from));
+            Ok(())
+        });
+
+        // reading from memory and writing to memory does not fail
+        debug_assert!(run.is_ok());
+        result
+    }
+}
+
+/************************************************************************************************/
+
+impl SedLines for String {
+    fn sed(self, script: &Script) -> Self {
+        self.split().sed(script).merge()
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Parser {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn commands(&mut self, nested: bool) -> Result<Vec<Command>, SedError> {
+        let mut commands = Vec::new();
+
+        loop {
+            while self.peek().is_some_and(|c| c.is_whitespace() || c == ';') {
+                self.position += 1;
+            }
+
+            match self.peek() {
+                None if nested => return Err(self.sed_error("missing '}'")),
+                None => return Ok(commands),
+                Some('}') if nested => {
+                    self.position += 1;
+                    return Ok(commands);
+                }
+                Some('}') => return Err(self.sed_error("unexpected '}'")),
+                Some('#') => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.position += 1;
+                    }
+                }
+                Some(_) => commands.push(self.command()?),
+            }
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn command(&mut self) -> Result<Command, SedError> {
+        let selector = self.selector()?;
+
+        self.skip_whitespace();
+        let (line, column) = self.line_column(self.position);
+        let kind = match self.next_char() {
+            Some('{') => CommandKind::Block(self.commands(true)?),
+            Some('s') => CommandKind::Substitute(self.substitute()?),
+            Some('y') => CommandKind::Transliterate(self.transliterate()?),
+            Some('a') => CommandKind::Append(self.text()),
+            Some('i') => CommandKind::Insert(self.text()),
+            Some('c') => CommandKind::Change(self.text()),
+            Some('d') => CommandKind::Delete,
+            Some('p') => CommandKind::Print,
+            Some('h') => CommandKind::Hold,
+            Some('H') => CommandKind::HoldAppend,
+            Some('g') => CommandKind::Get,
+            Some('G') => CommandKind::GetAppend,
+            Some('x') => CommandKind::Exchange,
+            Some('n') => CommandKind::Next,
+            Some('N') => CommandKind::NextAppend,
+            Some('=') => CommandKind::LineNumber,
+            Some('q') => CommandKind::Quit,
+            Some(c) => {
+                self.position -= 1;
+                return Err(self.sed_error(&format!("unknown command '{}'", c)));
+            }
+            None => return Err(self.sed_error("missing command")),
+        };
+
+        self.skip_whitespace();
+        match self.peek() {
+            None | Some('\n') | Some(';') | Some('}') | Some('#') => Ok(Command {
+                selector,
+                kind,
+                line,
+                column,
+            }),
+            Some(c) => Err(self.sed_error(&format!("unexpected '{}' after the command", c))),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn selector(&mut self) -> Result<Selector, SedError> {
+        let mut selector = Selector {
+            first: self.line_match()?,
+            last: None,
+            negated: false,
+            range: 0,
+        };
+
+        if selector.first.is_some() {
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.position += 1;
+                self.skip_whitespace();
+                selector.last = self.line_match()?;
+                if selector.last.is_none() {
+                    return Err(self.sed_error("missing the end of the range"));
+                }
+            }
+        }
+
+        self.skip_whitespace();
+        if self.peek() == Some('!') {
+            self.position += 1;
+            selector.negated = true;
+        }
+
+        Ok(selector)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn line_match(&mut self) -> Result<Option<Match>, SedError> {
+        let start = self.position;
+
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => {
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.position += 1;
+                }
+                match self.chars[start..self.position]
+                    .iter()
+                    .collect::<String>()
+                    .parse::<usize>()
+                {
+                    Ok(line) if line > 0 => Ok(Some(Match::Line(line))),
+                    _ => Err(self.error_at(start, "invalid line number")),
+                }
+            }
+            Some('$') => {
+                self.position += 1;
+                Ok(Some(Match::Last))
+            }
+            Some('/') => {
+                self.position += 1;
+                self.pattern(start, '/')
+                    .map(|pattern| Some(Match::Pattern(pattern)))
+            }
+            Some('\\') => {
+                self.position += 1;
+                match self.next_char() {
+                    Some(delimiter) if delimiter != '\n' && delimiter != '\\' => self
+                        .pattern(start, delimiter)
+                        .map(|pattern| Some(Match::Pattern(pattern))),
+                    _ => Err(self.error_at(start, "invalid pattern delimiter")),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn pattern(&mut self, start: usize, delimiter: char) -> Result<String, SedError> {
+        let pattern = self.closed(start, delimiter)?;
+
+        if !pattern.is_empty() {
+            Regex::new(&pattern).map_err(|_| self.error_at(start, "invalid pattern"))?;
+        }
+
+        Ok(pattern)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn substitute(&mut self) -> Result<Substitute, SedError> {
+        let start = self.position - 1;
+        let delimiter = match self.next_char() {
+            Some(c) if c != '\n' && c != '\\' => c,
+            _ => return Err(self.error_at(start, "invalid delimiter")),
+        };
+        let mut substitute = Substitute {
+            pattern: self.pattern(start, delimiter)?,
+            replacement: self.closed(start, delimiter)?,
+            global: false,
+            occurrence: 1,
+            print: false,
+            ignore_case: false,
+        };
+        let mut occurrence = String::new();
+
+        while let Some(c) = self.peek() {
+            match c {
+                'g' => substitute.global = true,
+                'p' => substitute.print = true,
+                'i' | 'I' => substitute.ignore_case = true,
+                c if c.is_ascii_digit() => occurrence.push(c),
+                _ => break,
+            }
+            self.position += 1;
+        }
+
+        if !occurrence.is_empty() {
+            substitute.occurrence = match occurrence.parse::<usize>() {
+                Ok(occurrence) if occurrence > 0 => occurrence,
+                _ => return Err(self.sed_error("invalid occurrence")),
+            };
+        }
+        if substitute.ignore_case && !substitute.pattern.is_empty() {
+            RegexBuilder::new(&substitute.pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|_| self.error_at(start, "invalid pattern"))?;
+        }
+
+        Ok(substitute)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn transliterate(&mut self) -> Result<Vec<(char, char)>, SedError> {
+        let start = self.position - 1;
+        let delimiter = match self.next_char() {
+            Some(c) if c != '\n' && c != '\\' => c,
+            _ => return Err(self.error_at(start, "invalid delimiter")),
+        };
+        let from: Vec<char> = unescape(&self.closed(start, delimiter)?).chars().collect();
+        let to: Vec<char> = unescape(&self.closed(start, delimiter)?).chars().collect();
+
+        if from.len() != to.len() {
+            return Err(self.error_at(start, "the lists have different lengths"));
+        }
+
+        Ok(from.into_iter().zip(to).collect())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the text of an `a`, `i` or `c` command, in the one line form `a text` or in the
+    /// form `a\` followed by the text on the next lines.
+    fn text(&mut self) -> String {
+        let mut text = String::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some('\\') {
+            self.position += 1;
+            if self.peek() == Some('\n') {
+                self.position += 1;
+            }
+        }
+
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            self.position += 1;
+            if c == '\\' {
+                match self.next_char() {
+                    Some(next) => text.push(next),
+                    None => text.push('\\'),
+                }
+            } else {
+                text.push(c);
+            }
+        }
+
+        text
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the text up to an unescaped delimiter like `delimited`, an error is returned when
+    /// the delimiter is missing.
+    fn closed(&mut self, start: usize, delimiter: char) -> Result<String, SedError> {
+        let mut text = String::new();
+
+        while let Some(c) = self.next_char() {
+            match c {
+                c if c == delimiter => return Ok(text),
+                '\\' if self.peek() == Some(delimiter) => {
+                    text.push(delimiter);
+                    self.position += 1;
+                }
+                '\\' => {
+                    text.push('\\');
+                    if let Some(next) = self.next_char() {
+                        text.push(next);
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+
+        Err(self.error_at(start, &format!("missing '{}'", delimiter)))
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn line_column(&self, position: usize) -> (usize, usize) {
+        let before = &self.chars[..position.min(self.chars.len())];
+        let line = before.iter().filter(|c| **c == '\n').count() + 1;
+        let column = before.iter().rev().take_while(|c| **c != '\n').count() + 1;
+
+        (line, column)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn error_at(&self, position: usize, message: &str) -> SedError {
+        let (line, column) = self.line_column(position);
+
+        SedError {
+            line,
+            column,
+            message: String::from(message),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn sed_error(&self, message: &str) -> SedError {
+        self.error_at(self.position, message)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The way a cycle ends.
+enum Flow {
+    /// Continues with the next command.
+    Continue,
+    /// Ends the cycle without printing the pattern space.
+    Delete,
+    /// Ends the cycle and the script.
+    Quit,
+}
+
+/************************************************************************************************/
+
+/// The state of a running script.
+struct Execution<'a, I: Iterator<Item = io::Result<String>>> {
+    script: &'a Script,
+    input: Peekable<I>,
+    line_number: usize,
+    pattern_space: String,
+    hold_space: String,
+    appended: StringLines,
+    ranges: HashMap<usize, bool>,
+    regexes: HashMap<(String, bool), Regex>,
+    last_pattern: Option<(String, bool)>,
+}
+
+/************************************************************************************************/
+
+impl<'a, I: Iterator<Item = io::Result<String>>> Execution<'a, I> {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(script: &'a Script, input: I) -> Self {
+        Execution {
+            script,
+            input: input.peekable(),
+            line_number: 0,
+            pattern_space: String::new(),
+            hold_space: String::new(),
+            appended: StringLines::new(),
+            ranges: HashMap::new(),
+            regexes: HashMap::new(),
+            last_pattern: None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn run<F>(&mut self, output: &mut F) -> io::Result<()>
+    where
+        F: FnMut(&str) -> io::Result<()>,
+    {
+        while let Some(line) = self.input.next() {
+            self.pattern_space = line?;
+            self.line_number += 1;
+
+            let flow = self.execute(&self.script.commands, output)?;
+            if !matches!(flow, Flow::Delete) && !self.script.quiet {
+                output(&self.pattern_space)?;
+            }
+            self.flush_appended(output)?;
+
+            if matches!(flow, Flow::Quit) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn execute<F>(&mut self, commands: &'a [Command], output: &mut F) -> io::Result<Flow>
+    where
+        F: FnMut(&str) -> io::Result<()>,
+    {
+        for command in commands.iter() {
+            if !self.selects(command) {
+                continue;
+            }
+
+            match &command.kind {
+                CommandKind::Block(commands) => match self.execute(commands, output)? {
+                    Flow::Continue => {}
+                    flow => return Ok(flow),
+                },
+                CommandKind::Substitute(substitute) => {
+                    if self.substitute(substitute) && substitute.print {
+                        output(&self.pattern_space)?;
+                    }
+                }
+                CommandKind::Transliterate(pairs) => {
+                    self.pattern_space = self
+                        .pattern_space
+                        .chars()
+                        .map(|c| pairs.iter().find(|(from, _)| *from == c).map_or(c, |p| p.1))
+                        .collect();
+                }
+                CommandKind::Delete => return Ok(Flow::Delete),
+                CommandKind::Print => output(&self.pattern_space)?,
+                CommandKind::Append(text) => self.appended.push(text.clone()),
+                CommandKind::Insert(text) => output(text)?,
+                CommandKind::Change(text) => {
+                    let in_range = command.selector.last.is_some() && !command.selector.negated;
+                    if !in_range
+                        || !self
+                            .ranges
+                            .get(&command.selector.range)
+                            .copied()
+                            .unwrap_or(false)
+                    {
+                        output(text)?;
+                    }
+                    return Ok(Flow::Delete);
+                }
+                CommandKind::Hold => self.hold_space = self.pattern_space.clone(),
+                CommandKind::HoldAppend => {
+                    self.hold_space.push('\n');
+                    self.hold_space.push_str(&self.pattern_space);
+                }
+                CommandKind::Get => self.pattern_space = self.hold_space.clone(),
+                CommandKind::GetAppend => {
+                    self.pattern_space.push('\n');
+                    self.pattern_space.push_str(&self.hold_space);
+                }
+                CommandKind::Exchange => {
+                    std::mem::swap(&mut self.pattern_space, &mut self.hold_space)
+                }
+                CommandKind::Next => {
+                    if self.input.peek().is_none() {
+                        return Ok(Flow::Quit);
+                    }
+                    if !self.script.quiet {
+                        output(&self.pattern_space)?;
+                    }
+                    self.flush_appended(output)?;
+                    self.read_line()?;
+                }
+                CommandKind::NextAppend => {
+                    if self.input.peek().is_none() {
+                        return Ok(Flow::Quit);
+                    }
+                    self.flush_appended(output)?;
+                    let previous = std::mem::take(&mut self.pattern_space);
+                    self.read_line()?;
+                    self.pattern_space = format!("{}\n{}", previous, self.pattern_space);
+                }
+                CommandKind::LineNumber => output(&self.line_number.to_string())?,
+                CommandKind::Quit => return Ok(Flow::Quit),
+            }
+        }
+
+        Ok(Flow::Continue)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when a command is executed on the current line. The state of ranges is
+    /// kept per range, a range is active while it is still open after the current line.
+    fn selects(&mut self, command: &Command) -> bool {
+        let selector = &command.selector;
+        let selected = match (&selector.first, &selector.last) {
+            (None, _) => true,
+            (Some(first), None) => self.matches(first),
+            (Some(first), Some(last)) => {
+                let key = selector.range;
+                let active = self.ranges.get(&key).copied().unwrap_or(false);
+
+                if active {
+                    let open = match last {
+                        Match::Line(line) => self.line_number < *line,
+                        _ => !self.matches(last),
+                    };
+                    self.ranges.insert(key, open);
+                    true
+                } else if self.matches(first) {
+                    let open = match last {
+                        Match::Line(line) => self.line_number < *line,
+                        Match::Last => !self.is_last(),
+                        Match::Pattern(_) => true,
+                    };
+                    self.ranges.insert(key, open);
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+
+        selected != selector.negated
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn matches(&mut self, line_match: &Match) -> bool {
+        match line_match {
+            Match::Line(line) => self.line_number == *line,
+            Match::Last => self.is_last(),
+            Match::Pattern(pattern) => match self.regex(pattern, false) {
+                Some(regex) => regex.is_match(&self.pattern_space),
+                None => false,
+            },
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn substitute(&mut self, substitute: &Substitute) -> bool {
+        let regex = match self.regex(&substitute.pattern, substitute.ignore_case) {
+            Some(regex) => regex,
+            None => return false,
+        };
+        let mut result = String::with_capacity(self.pattern_space.len());
+        let mut last = 0;
+        let mut replaced = false;
+
+        for (index, captures) in regex.captures_iter(&self.pattern_space).enumerate() {
+            if index + 1 < substitute.occurrence {
+                continue;
+            }
+            if let Some(whole) = captures.get(0) {
+                result.push_str(&self.pattern_space[last..whole.start()]);
+                expand(&captures, &substitute.replacement, &mut result);
+                last = whole.end();
+                replaced = true;
+            }
+            if !substitute.global {
+                break;
+            }
+        }
+
+        if replaced {
+            result.push_str(&self.pattern_space[last..]);
+            self.pattern_space = result;
+        }
+
+        replaced
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the compiled regular expression, an empty pattern returns the last used one.
+    fn regex(&mut self, pattern: &str, ignore_case: bool) -> Option<Regex> {
+        let key = if pattern.is_empty() {
+            self.last_pattern.clone()?
+        } else {
+            (String::from(pattern), ignore_case)
+        };
+
+        if !self.regexes.contains_key(&key) {
+            let regex = RegexBuilder::new(&key.0)
+                .case_insensitive(key.1)
+                .build()
+                .ok()?;
+            self.regexes.insert(key.clone(), regex);
+        }
+
+        self.last_pattern = Some(key.clone());
+        self.regexes.get(&key).cloned()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn is_last(&mut self) -> bool {
+        self.input.peek().is_none()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn read_line(&mut self) -> io::Result<()> {
+        if let Some(line) = self.input.next() {
+            self.pattern_space = line?;
+            self.line_number += 1;
+        }
+
+        Ok(())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn flush_appended<F>(&mut self, output: &mut F) -> io::Result<()>
+    where
+        F: FnMut(&str) -> io::Result<()>,
+    {
+        for text in self.appended.drain(..) {
+            output(&text)?;
+        }
+
+        Ok(())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Appends the replacement of a match, with `&` replaced by the match, `\1` to `\9` by the
+/// groups and `\n` by a newline.
+fn expand(captures: &Captures, replacement: &str, result: &mut String) {
+    let mut chars = replacement.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => result.push_str(captures.get(0).map_or("", |m| m.as_str())),
+            '\\' => match chars.next() {
+                Some(d) if d.is_ascii_digit() => {
+                    let group = d as usize - '0' as usize;
+                    result.push_str(captures.get(group).map_or("", |m| m.as_str()));
+                }
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            },
+            c => result.push(c),
+        }
+    }
+}
+
+/************************************************************************************************/
+
+/// Gives every range in the commands and the blocks within them the next index.
+fn number_ranges(commands: &mut [Command], next: &mut usize) {
+    for command in commands.iter_mut() {
+        if command.selector.last.is_some() {
+            command.selector.range = *next;
+            *next += 1;
+        }
+        if let CommandKind::Block(commands) = &mut command.kind {
+            number_ranges(commands, next);
+        }
+    }
+}
+
+/************************************************************************************************/
+
+/// Replaces the escapes `\n`, `\t` and `\\` by the characters they stand for.
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,646 @@
+//! `Sloc` is a module that contains traits to classify source lines as blank, comment or code
+//! lines and to count them per file, like `cloc`.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::align::Alignment;
+use crate::lines::*;
+use crate::table::*;
+use std::ops::{Add, AddAssign};
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The kind of a source line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// The line is empty or contains only whitespace.
+    Blank,
+    /// The line contains only comments.
+    Comment,
+    /// The line contains only code.
+    Code,
+    /// The line contains both code and comments.
+    Mixed,
+}
+
+/************************************************************************************************/
+
+/// The comment syntax of a language. Text within string literals is code, even when it looks
+/// like a comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Syntax {
+    /// The name of the language.
+    pub name: String,
+    /// The file extensions of the language, without a leading dot.
+    pub extensions: Vec<String>,
+    /// The tokens that start a comment running to the end of the line.
+    pub line_comments: Vec<String>,
+    /// The opening and closing delimiters of block comments.
+    pub block_comments: Vec<(String, String)>,
+    /// Block comments can be nested when `true`.
+    pub nested_comments: bool,
+    /// The opening and closing delimiters of string literals.
+    pub strings: Vec<(String, String)>,
+    /// The opening and closing delimiters of raw string literals, which have no escapes.
+    pub raw_strings: Vec<(String, String)>,
+    /// The character that escapes the next character within a string literal.
+    pub escape: Option<char>,
+    /// Single quotes start character literals like `'"'` when `true`, a quote that is not
+    /// followed by a character literal, like the one of a lifetime, is code.
+    pub char_literals: bool,
+}
+
+/************************************************************************************************/
+
+/// The amount of lines of every kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SlocReport {
+    /// The amount of blank lines.
+    pub blank: usize,
+    /// The amount of comment lines.
+    pub comment: usize,
+    /// The amount of code lines.
+    pub code: usize,
+    /// The amount of lines with both code and comments.
+    pub mixed: usize,
+}
+
+/************************************************************************************************/
+
+/// The `SlocLines` trait contains all functions to be implemented that classify and count
+/// source lines.
+pub trait SlocLines {
+    /// Returns the kind of every line. Block comments and string literals can span multiple
+    /// lines, blank lines within them are still blank.
+    ///
+    /// # Arguments
+    ///
+    /// * `syntax` - the comment syntax of the language.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::sloc::*;
+    ///
+    /// let s = String::from("/* a\n   b */\nlet s = \"//\"; // c\n\nf();");
+    /// let rust = Syntax::preset("rust").unwrap();
+    ///
+    /// assert_eq!(
+    ///     s.classify(&rust),
+    ///     vec![
+    ///         LineKind::Comment,
+    ///         LineKind::Comment,
+    ///         LineKind::Mixed,
+    ///         LineKind::Blank,
+    ///         LineKind::Code
+    ///     ]
+    /// );
+    /// ```
+    fn classify(&self, syntax: &Syntax) -> Vec<LineKind>;
+
+    /// Returns the amount of lines of every kind, the total equals the amount of lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `syntax` - the comment syntax of the language.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::lines::*;
+    /// use rich_lib::sloc::*;
+    ///
+    /// let s = String::from("# setup\nimport os  # os\n\nos.sync()");
+    /// let report = s.sloc(&Syntax::preset("py").unwrap());
+    ///
+    /// assert_eq!((report.blank, report.comment, report.code, report.mixed), (1, 1, 1, 1));
+    /// assert_eq!(report.total(), s.count());
+    /// ```
+    fn sloc(&self, syntax: &Syntax) -> SlocReport;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Renders the reports of a set of files as a table, followed by a row with the totals.
+///
+/// # Arguments
+///
+/// * `files` - the names of the files and their reports.
+/// * `style` - the style of the borders.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::sloc::*;
+/// use rich_lib::table::*;
+///
+/// let files = vec![
+///     (String::from("main.rs"), SlocReport { blank: 2, comment: 4, code: 30, mixed: 1 }),
+///     (String::from("lib.rs"), SlocReport { blank: 1, comment: 0, code: 9, mixed: 0 }),
+/// ];
+///
+/// assert_eq!(
+///     render_report(&files, TableStyle::Markdown),
+///     vec![
+///         "| File    | Blank | Comment | Code | Mixed |",
+///         "| ------- | ----: | ------: | ---: | ----: |",
+///         "| main.rs |     2 |       4 |   30 |     1 |",
+///         "| lib.rs  |     1 |       0 |    9 |     0 |",
+///         "| Total   |     3 |       4 |   39 |     1 |",
+///     ]
+/// );
+/// ```
+pub fn render_report(files: &[(String, SlocReport)], style: TableStyle) -> StringLines {
+    fn row(name: &str, report: &SlocReport) -> Vec<String> {
+        vec![
+            String::from(name),
+            report.blank.to_string(),
+            report.comment.to_string(),
+            report.code.to_string(),
+            report.mixed.to_string(),
+        ]
+    }
+
+    let total = files
+        .iter()
+        .fold(SlocReport::new(), |total, (_, report)| total + *report);
+    let mut rows: Vec<Vec<String>> = files
+        .iter()
+        .map(|(name, report)| row(name, report))
+        .collect();
+    rows.push(row("Total", &total));
+
+    let header = ["File", "Blank", "Comment", "Code", "Mixed"];
+    let mut alignments = vec![Alignment::Right; header.len()];
+    alignments[0] = Alignment::Left;
+
+    Table::new(rows)
+        .header(header.iter().map(|cell| String::from(*cell)).collect())
+        .render(&TableOptions::new(style).alignments(alignments))
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Syntax {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a syntax without comments and string literals, every non-blank line is code.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the language.
+    pub fn new(name: &str) -> Self {
+        Syntax {
+            name: String::from(name),
+            extensions: Vec::new(),
+            line_comments: Vec::new(),
+            block_comments: Vec::new(),
+            nested_comments: false,
+            strings: Vec::new(),
+            raw_strings: Vec::new(),
+            escape: Some('\\'),
+            char_literals: false,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Adds file extensions of the language.
+    pub fn extensions(mut self, extensions: &[&str]) -> Self {
+        self.extensions
+            .extend(extensions.iter().map(|extension| String::from(*extension)));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Adds a token that starts a comment running to the end of the line.
+    pub fn line_comment(mut self, token: &str) -> Self {
+        self.line_comments.push(String::from(token));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Adds the delimiters of a block comment.
+    pub fn block_comment(mut self, open: &str, close: &str) -> Self {
+        self.block_comments
+            .push((String::from(open), String::from(close)));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether block comments can be nested.
+    pub fn nested_comments(mut self, nested: bool) -> Self {
+        self.nested_comments = nested;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Adds the delimiters of a string literal.
+    pub fn string(mut self, open: &str, close: &str) -> Self {
+        self.strings.push((String::from(open), String::from(close)));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Adds the delimiters of a raw string literal.
+    pub fn raw_string(mut self, open: &str, close: &str) -> Self {
+        self.raw_strings
+            .push((String::from(open), String::from(close)));
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the character that escapes the next character within a string literal.
+    pub fn escape(mut self, escape: Option<char>) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether single quotes start character literals.
+    pub fn char_literals(mut self, char_literals: bool) -> Self {
+        self.char_literals = char_literals;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the built-in syntaxes of common languages.
+    pub fn presets() -> Vec<Syntax> {
+        fn c_like(name: &str, extensions: &[&str]) -> Syntax {
+            Syntax::new(name)
+                .extensions(extensions)
+                .line_comment("//")
+                .block_comment("/*", "*/")
+                .string("\"", "\"")
+                .string("'", "'")
+        }
+
+        vec![
+            Syntax::new("Rust")
+                .extensions(&["rs"])
+                .line_comment("//")
+                .block_comment("/*", "*/")
+                .nested_comments(true)
+                .string("\"", "\"")
+                .raw_string("r\"", "\"")
+                .raw_string("r#\"", "\"#")
+                .raw_string("r##\"", "\"##")
+                .raw_string("r###\"", "\"###")
+                .char_literals(true),
+            c_like("C", &["c", "h"]),
+            c_like("C++", &["cc", "cpp", "cxx", "hpp"]).raw_string("R\"(", ")\""),
+            c_like("C#", &["cs"]).raw_string("@\"", "\""),
+            c_like("Go", &["go"]).raw_string("`", "`"),
+            c_like("Java", &["java"]).string("\"\"\"", "\"\"\""),
+            c_like("JavaScript", &["js", "mjs", "cjs"]).string("`", "`"),
+            c_like("TypeScript", &["ts", "tsx"]).string("`", "`"),
+            c_like("Kotlin", &["kt", "kts"])
+                .nested_comments(true)
+                .raw_string("\"\"\"", "\"\"\""),
+            Syntax::new("Swift")
+                .extensions(&["swift"])
+                .line_comment("//")
+                .block_comment("/*", "*/")
+                .nested_comments(true)
+                .string("\"\"\"", "\"\"\"")
+                .string("\"", "\""),
+            Syntax::new("Python")
+                .extensions(&["py"])
+                .line_comment("#")
+                .string("\"\"\"", "\"\"\"")
+                .string("'''", "'''")
+                .string("\"", "\"")
+                .string("'", "'"),
+            Syntax::new("Shell")
+                .extensions(&["sh", "bash", "zsh"])
+                .line_comment("#")
+                .string("\"", "\"")
+                .string("'", "'"),
+            Syntax::new("INI")
+                .extensions(&["ini", "cfg"])
+                .line_comment("#")
+                .line_comment(";"),
+            Syntax::new("SQL")
+                .extensions(&["sql"])
+                .line_comment("--")
+                .block_comment("/*", "*/")
+                .string("'", "'"),
+            Syntax::new("HTML")
+                .extensions(&["html", "htm", "xml", "svg"])
+                .block_comment("<!--", "-->"),
+            Syntax::new("Lua")
+                .extensions(&["lua"])
+                .line_comment("--")
+                .block_comment("--[[", "]]")
+                .string("\"", "\"")
+                .string("'", "'"),
+            Syntax::new("Haskell")
+                .extensions(&["hs"])
+                .line_comment("--")
+                .block_comment("{-", "-}")
+                .nested_comments(true)
+                .string("\"", "\""),
+        ]
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the built-in syntax with a given name or file extension, both are compared case
+    /// insensitively.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the language or a file extension without a leading dot.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::sloc::*;
+    ///
+    /// assert_eq!(Syntax::preset("java").unwrap().name, "Java");
+    /// assert_eq!(Syntax::preset("c++").unwrap().extensions, vec!["cc", "cpp", "cxx", "hpp"]);
+    /// assert_eq!(Syntax::preset("Python").unwrap().line_comments, vec!["#"]);
+    /// assert!(Syntax::preset("unknown").is_none());
+    /// ```
+    pub fn preset(name: &str) -> Option<Syntax> {
+        Syntax::presets().into_iter().find(|syntax| {
+            syntax.name.eq_ignore_ascii_case(name)
+                || syntax
+                    .extensions
+                    .iter()
+                    .any(|extension| extension.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl SlocReport {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        SlocReport::default()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Counts a single line of a given kind.
+    pub fn record(&mut self, kind: LineKind) {
+        match kind {
+            LineKind::Blank => self.blank += 1,
+            LineKind::Comment => self.comment += 1,
+            LineKind::Code => self.code += 1,
+            LineKind::Mixed => self.mixed += 1,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the total amount of lines.
+    pub fn total(&self) -> usize {
+        self.blank + self.comment + self.code + self.mixed
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Add for SlocReport {
+    type Output = SlocReport;
+
+    fn add(mut self, other: SlocReport) -> SlocReport {
+        self += other;
+        self
+    }
+}
+
+/************************************************************************************************/
+
+impl AddAssign for SlocReport {
+    fn add_assign(&mut self, other: SlocReport) {
+        self.blank += other.blank;
+        self.comment += other.comment;
+        self.code += other.code;
+        self.mixed += other.mixed;
+    }
+}
+
+/************************************************************************************************/
+
+impl SlocLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn classify(&self, syntax: &Syntax) -> Vec<LineKind> {
+        let mut classifier = Classifier::new(syntax);
+        self.iter().map(|line| classifier.classify(line)).collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn sloc(&self, syntax: &Syntax) -> SlocReport {
+        let mut report = SlocReport::new();
+        for kind in self.classify(syntax) {
+            report.record(kind);
+        }
+        report
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl SlocLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn classify(&self, syntax: &Syntax) -> Vec<LineKind> {
+        self.split().classify(syntax)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn sloc(&self, syntax: &Syntax) -> SlocReport {
+        self.split().sloc(syntax)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Classifies lines one by one, keeping track of open block comments and string literals.
+struct Classifier<'a> {
+    syntax: &'a Syntax,
+    /// The opening and closing delimiters of the string literals and whether they have escapes,
+    /// the longest opening delimiters first.
+    strings: Vec<(&'a str, &'a str, bool)>,
+    /// The index and depth of the open block comment.
+    comment: Option<(usize, usize)>,
+    /// The index of the open string literal.
+    string: Option<usize>,
+}
+
+/************************************************************************************************/
+
+impl<'a> Classifier<'a> {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(syntax: &'a Syntax) -> Self {
+        let mut strings: Vec<(&str, &str, bool)> = syntax
+            .strings
+            .iter()
+            .map(|(open, close)| (open.as_str(), close.as_str(), true))
+            .chain(
+                syntax
+                    .raw_strings
+                    .iter()
+                    .map(|(open, close)| (open.as_str(), close.as_str(), false)),
+            )
+            .collect();
+        strings.sort_by_key(|(open, _, _)| std::cmp::Reverse(open.len()));
+
+        Classifier {
+            syntax,
+            strings,
+            comment: None,
+            string: None,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn classify(&mut self, line: &str) -> LineKind {
+        let mut code = false;
+        let mut comment = false;
+        let mut rest = line;
+
+        while let Some(c) = rest.chars().next() {
+            if let Some((index, depth)) = self.comment {
+                comment = true;
+                let (open, close) = &self.syntax.block_comments[index];
+                if rest.starts_with(close.as_str()) {
+                    self.comment = if depth > 1 {
+                        Some((index, depth - 1))
+                    } else {
+                        None
+                    };
+                    rest = &rest[close.len()..];
+                } else if self.syntax.nested_comments && rest.starts_with(open.as_str()) {
+                    self.comment = Some((index, depth + 1));
+                    rest = &rest[open.len()..];
+                } else {
+                    rest = &rest[c.len_utf8()..];
+                }
+            } else if let Some(index) = self.string {
+                code = true;
+                let (_, close, escaped) = self.strings[index];
+                if escaped && self.syntax.escape == Some(c) {
+                    let mut chars = rest.chars();
+                    chars.next();
+                    chars.next();
+                    rest = chars.as_str();
+                } else if rest.starts_with(close) {
+                    self.string = None;
+                    rest = &rest[close.len()..];
+                } else {
+                    rest = &rest[c.len_utf8()..];
+                }
+            } else if c.is_whitespace() {
+                rest = &rest[c.len_utf8()..];
+            } else if let Some(index) = self
+                .syntax
+                .block_comments
+                .iter()
+                .position(|(open, _)| rest.starts_with(open.as_str()))
+            {
+                comment = true;
+                self.comment = Some((index, 1));
+                rest = &rest[self.syntax.block_comments[index].0.len()..];
+            } else if self
+                .syntax
+                .line_comments
+                .iter()
+                .any(|token| rest.starts_with(token.as_str()))
+            {
+                comment = true;
+                break;
+            } else if let Some(index) = self
+                .strings
+                .iter()
+                .position(|(open, _, _)| rest.starts_with(open))
+            {
+                code = true;
+                self.string = Some(index);
+                rest = &rest[self.strings[index].0.len()..];
+            } else if let Some(length) = self.char_literal(rest) {
+                code = true;
+                rest = &rest[length..];
+            } else {
+                code = true;
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+
+        if line.trim().is_empty() {
+            LineKind::Blank
+        } else if code && comment {
+            LineKind::Mixed
+        } else if code {
+            LineKind::Code
+        } else {
+            LineKind::Comment
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the length of the character literal at the start of a text, `None` when the
+    /// text does not start with one, like a lifetime or a label.
+    fn char_literal(&self, text: &str) -> Option<usize> {
+        if !self.syntax.char_literals || !text.starts_with('\'') {
+            return None;
+        }
+
+        let mut chars = text.char_indices().skip(1);
+        let (_, c) = chars.next()?;
+        if self.syntax.escape == Some(c) {
+            chars.next()?;
+            chars
+                .find(|(_, c)| *c == '\'')
+                .map(|(offset, _)| offset + 1)
+        } else {
+            match chars.next() {
+                Some((offset, '\'')) => Some(offset + 1),
+                _ => None,
+            }
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,860 @@
+//! `Table` is a module that renders rows of cells as tables with ASCII, Unicode box-drawing or
+//! Markdown borders, and parses such tables back into rows of cells.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::align::*;
+use crate::diff::Overflow;
+use crate::lines::*;
+use crate::width::*;
+use crate::wrap::*;
+use std::fmt;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The style of the borders of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableStyle {
+    /// Borders made of `+`, `-`, `=` and `|`, like the grid tables of Pandoc.
+    Ascii,
+    /// Borders made of box-drawing characters.
+    Unicode,
+    /// A Markdown pipe table, line breaks in cells are rendered as `<br>`.
+    Markdown,
+}
+
+/************************************************************************************************/
+
+/// A table of cells, a cell may contain multiple lines.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Table {
+    /// The cells of the header row, if any.
+    pub header: Option<Vec<String>>,
+    /// The cells of the body rows. Rows may have different amounts of cells, missing cells are
+    /// rendered empty.
+    pub rows: Vec<Vec<String>>,
+}
+
+/************************************************************************************************/
+
+/// The options used when rendering a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableOptions {
+    /// The style of the borders.
+    pub style: TableStyle,
+    /// The alignment of each column, columns without an alignment are aligned to the left.
+    pub alignments: Vec<Alignment>,
+    /// The maximum display width of each column, columns without a maximum width fit their
+    /// widest line.
+    pub max_widths: Vec<Option<usize>>,
+    /// The way cell lines that are wider than their column are rendered.
+    pub overflow: Overflow,
+    /// Draws a line between body rows when `true`. Grid tables always draw these lines when a
+    /// body row spans multiple lines, so the rows can be told apart. Ignored by the Markdown
+    /// style.
+    pub row_lines: bool,
+}
+
+/************************************************************************************************/
+
+/// A table found in lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableBlock {
+    /// The range of the lines of the table.
+    pub range: Range<usize>,
+    /// The style of the borders.
+    pub style: TableStyle,
+    /// The alignment of each column given by a Markdown separator row, empty for grid tables.
+    pub alignments: Vec<Alignment>,
+    /// The parsed table.
+    pub table: Table,
+}
+
+/************************************************************************************************/
+
+/// The error returned when parsing a table fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableError {
+    /// The (one based) line of the problem.
+    pub line: usize,
+    /// The description of the problem.
+    pub message: String,
+}
+
+/************************************************************************************************/
+
+/// The `TableLines` trait contains all functions to be implemented that find tables in lines.
+pub trait TableLines {
+    /// Returns all Markdown pipe tables and ASCII or Unicode grid tables. A grid table starts
+    /// with a top border, a Markdown table with a header row followed by a separator row.
+    /// Candidate blocks that do not parse as a table are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::align::*;
+    /// use rich_lib::table::*;
+    ///
+    /// let s = String::from("Text\n\n| a | b \\| c |\n|---|--:|\n| 1 | 2 |\n\nMore text");
+    /// let tables = s.tables();
+    ///
+    /// assert_eq!(tables.len(), 1);
+    /// assert_eq!(tables[0].range, 2..5);
+    /// assert_eq!(tables[0].alignments, vec![Alignment::Left, Alignment::Right]);
+    /// assert_eq!(tables[0].table.header, Some(vec![String::from("a"), String::from("b | c")]));
+    /// assert_eq!(tables[0].table.rows, vec![vec![String::from("1"), String::from("2")]]);
+    /// ```
+    fn tables(&self) -> Vec<TableBlock>;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Table {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates a table without a header.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - the cells of the body rows.
+    pub fn new(rows: Vec<Vec<String>>) -> Self {
+        Table { header: None, rows }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the cells of the header row.
+    pub fn header(mut self, header: Vec<String>) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the amount of columns, the amount of cells of the longest row.
+    pub fn columns(&self) -> usize {
+        self.header
+            .iter()
+            .chain(self.rows.iter())
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Renders the table. An empty table renders no lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for rendering.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::align::*;
+    /// use rich_lib::table::*;
+    ///
+    /// let table = Table::new(vec![
+    ///     vec![String::from("apple"), String::from("3")],
+    ///     vec![String::from("kiwi"), String::from("12")],
+    /// ])
+    /// .header(vec![String::from("fruit"), String::from("count")]);
+    /// let options = TableOptions::new(TableStyle::Unicode)
+    ///     .alignments(vec![Alignment::Left, Alignment::Right]);
+    ///
+    /// assert_eq!(
+    ///     table.render(&options),
+    ///     vec![
+    ///         "┌───────┬───────┐",
+    ///         "│ fruit │ count │",
+    ///         "╞═══════╪═══════╡",
+    ///         "│ apple │     3 │",
+    ///         "│ kiwi  │    12 │",
+    ///         "└───────┴───────┘",
+    ///     ]
+    /// );
+    /// ```
+    pub fn render(&self, options: &TableOptions) -> StringLines {
+        let columns = self.columns();
+        if columns == 0 {
+            return StringLines::new();
+        }
+
+        let header = self
+            .header
+            .as_ref()
+            .map(|row| layout(row, columns, options));
+        let rows: Vec<Vec<StringLines>> = self
+            .rows
+            .iter()
+            .map(|row| layout(row, columns, options))
+            .collect();
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                let width = header
+                    .iter()
+                    .chain(rows.iter())
+                    .flat_map(|row| row[column].iter())
+                    .map(|line| display_width(line))
+                    .max()
+                    .unwrap_or(0);
+                match options.style {
+                    TableStyle::Markdown => width.max(3),
+                    _ => width.max(1),
+                }
+            })
+            .collect();
+
+        match options.style {
+            TableStyle::Markdown => render_markdown(header.as_ref(), &rows, &widths, options),
+            TableStyle::Ascii => render_grid(header.as_ref(), &rows, &widths, options, &ASCII),
+            TableStyle::Unicode => render_grid(header.as_ref(), &rows, &widths, options, &UNICODE),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Parses a Markdown pipe table or an ASCII or Unicode grid table that spans all lines.
+    /// Like in Pandoc grid tables, the lines between two horizontal lines form a single row, so
+    /// a grid table rendered without row lines has one body row. The lines of a multi-line cell
+    /// are joined with newlines, like `<br>` in a Markdown cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - the lines of the table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::lines::*;
+    /// use rich_lib::table::*;
+    ///
+    /// let lines = String::from(
+    ///     "+-----+-------+\n\
+    ///      | key | value |\n\
+    ///      +=====+=======+\n\
+    ///      | a   | two   |\n\
+    ///      |     | lines |\n\
+    ///      +-----+-------+\n\
+    ///      | b   | x|y   |\n\
+    ///      +-----+-------+",
+    /// )
+    /// .split();
+    /// let table = Table::parse(&lines).unwrap();
+    ///
+    /// assert_eq!(table.header, Some(vec![String::from("key"), String::from("value")]));
+    /// assert_eq!(table.rows[0], vec!["a", "two\nlines"]);
+    /// assert_eq!(table.rows[1], vec!["b", "x|y"]);
+    /// ```
+    pub fn parse(lines: &[String]) -> Result<Self, TableError> {
+        let first = lines
+            .first()
+            .ok_or_else(|| TableError::new(0, "there is no table"))?;
+        let block = if is_rule(first) {
+            parse_grid(lines, 0)?
+        } else {
+            parse_markdown(lines, 0)?
+        };
+
+        match lines.get(block.range.end) {
+            Some(_) => Err(TableError::new(block.range.end + 1, "text after the table")),
+            None => Ok(block.table),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl From<Vec<Vec<String>>> for Table {
+    fn from(rows: Vec<Vec<String>>) -> Self {
+        Table::new(rows)
+    }
+}
+
+/************************************************************************************************/
+
+impl TableOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options for a style, which align columns to the left, do not limit
+    /// their width and draw no lines between body rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - the style of the borders.
+    pub fn new(style: TableStyle) -> Self {
+        TableOptions {
+            style,
+            alignments: Vec::new(),
+            max_widths: Vec::new(),
+            overflow: Overflow::Wrap,
+            row_lines: false,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the alignment of each column.
+    pub fn alignments(mut self, alignments: Vec<Alignment>) -> Self {
+        self.alignments = alignments;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the maximum display width of each column.
+    pub fn max_widths(mut self, max_widths: Vec<Option<usize>>) -> Self {
+        self.max_widths = max_widths;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the way cell lines that are wider than their column are rendered.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether a line is drawn between body rows.
+    pub fn row_lines(mut self, row_lines: bool) -> Self {
+        self.row_lines = row_lines;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn alignment(&self, column: usize) -> Alignment {
+        self.alignments
+            .get(column)
+            .copied()
+            .unwrap_or(Alignment::Left)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        TableOptions::new(TableStyle::Ascii)
+    }
+}
+
+/************************************************************************************************/
+
+impl TableBlock {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the options that render the table in its original style and alignment.
+    pub fn options(&self) -> TableOptions {
+        TableOptions::new(self.style).alignments(self.alignments.clone())
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl TableError {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(line: usize, message: &str) -> Self {
+        TableError {
+            line,
+            message: String::from(message),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            0 => write!(f, "invalid table: {}", self.message),
+            line => write!(f, "line {}: {}", line, self.message),
+        }
+    }
+}
+
+/************************************************************************************************/
+
+impl std::error::Error for TableError {}
+
+/************************************************************************************************/
+
+impl TableLines for StringLines {
+    fn tables(&self) -> Vec<TableBlock> {
+        let mut tables = Vec::new();
+        let mut index = 0;
+
+        while index < self.len() {
+            let block = if is_rule(&self[index]) {
+                parse_grid(self, index).ok()
+            } else if is_markdown_start(self, index) {
+                parse_markdown(self, index).ok()
+            } else {
+                None
+            };
+
+            match block {
+                Some(block) => {
+                    index = block.range.end;
+                    tables.push(block);
+                }
+                None => index += 1,
+            }
+        }
+
+        tables
+    }
+}
+
+/************************************************************************************************/
+
+impl TableLines for String {
+    fn tables(&self) -> Vec<TableBlock> {
+        self.split().tables()
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The characters of the borders of a grid table.
+struct Border {
+    /// The left, fill, middle and right character of the top line.
+    top: [char; 4],
+    /// The characters of the line below the header.
+    header: [char; 4],
+    /// The characters of the lines between body rows.
+    row: [char; 4],
+    /// The characters of the bottom line.
+    bottom: [char; 4],
+    /// The character of the vertical lines.
+    vertical: char,
+}
+
+/************************************************************************************************/
+
+const ASCII: Border = Border {
+    top: ['+', '-', '+', '+'],
+    header: ['+', '=', '+', '+'],
+    row: ['+', '-', '+', '+'],
+    bottom: ['+', '-', '+', '+'],
+    vertical: '|',
+};
+
+/************************************************************************************************/
+
+const UNICODE: Border = Border {
+    top: ['┌', '─', '┬', '┐'],
+    header: ['╞', '═', '╪', '╡'],
+    row: ['├', '─', '┼', '┤'],
+    bottom: ['└', '─', '┴', '┘'],
+    vertical: '│',
+};
+
+/************************************************************************************************/
+
+/// Returns the lines of every cell of a row, fitted to the maximum width of its column. Markdown
+/// cells are escaped and joined into a single line.
+fn layout(row: &[String], columns: usize, options: &TableOptions) -> Vec<StringLines> {
+    (0..columns)
+        .map(|column| {
+            let cell = row.get(column).map_or("", String::as_str);
+            let max_width = options.max_widths.get(column).copied().flatten();
+            let mut lines = StringLines::new();
+
+            for line in cell.lines() {
+                match max_width {
+                    Some(max_width) if display_width(line) > max_width => match options.overflow {
+                        Overflow::Truncate => lines.push(truncate_text(
+                            line,
+                            max_width,
+                            &WidthOptions::new().ellipsis(""),
+                        )),
+                        Overflow::Wrap => {
+                            lines.extend(wrap_paragraph(line, &WrapOptions::new(max_width.max(1))))
+                        }
+                    },
+                    _ => lines.push(String::from(line)),
+                }
+            }
+
+            match options.style {
+                TableStyle::Markdown => vec![lines.join("<br>").replace('|', "\\|")],
+                _ if lines.is_empty() => vec![String::new()],
+                _ => lines,
+            }
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Returns a horizontal line of a grid table.
+fn rule(widths: &[usize], [left, fill, middle, right]: [char; 4]) -> String {
+    let cells: Vec<String> = widths
+        .iter()
+        .map(|width| fill.to_string().repeat(width + 2))
+        .collect();
+
+    format!("{}{}{}", left, cells.join(&middle.to_string()), right)
+}
+
+/************************************************************************************************/
+
+/// Returns the lines of a row of a grid table.
+fn grid_row(
+    row: &[StringLines],
+    widths: &[usize],
+    options: &TableOptions,
+    vertical: char,
+) -> StringLines {
+    let height = row.iter().map(Vec::len).max().unwrap_or(1);
+
+    (0..height)
+        .map(|line| {
+            let cells: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(column, lines)| {
+                    let text = lines.get(line).map_or("", String::as_str);
+                    pad(text, widths[column], options.alignment(column), true)
+                })
+                .collect();
+            let separator = format!(" {} ", vertical);
+            format!("{} {} {}", vertical, cells.join(&separator), vertical)
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+fn render_grid(
+    header: Option<&Vec<StringLines>>,
+    rows: &[Vec<StringLines>],
+    widths: &[usize],
+    options: &TableOptions,
+    border: &Border,
+) -> StringLines {
+    let mut result = vec![rule(widths, border.top)];
+    let row_lines = options.row_lines
+        || rows
+            .iter()
+            .any(|row| row.iter().any(|lines| lines.len() > 1));
+
+    if let Some(header) = header {
+        result.extend(grid_row(header, widths, options, border.vertical));
+        result.push(rule(widths, border.header));
+    }
+    for (index, row) in rows.iter().enumerate() {
+        if index > 0 && row_lines {
+            result.push(rule(widths, border.row));
+        }
+        result.extend(grid_row(row, widths, options, border.vertical));
+    }
+
+    result.push(rule(widths, border.bottom));
+    result
+}
+
+/************************************************************************************************/
+
+fn render_markdown(
+    header: Option<&Vec<StringLines>>,
+    rows: &[Vec<StringLines>],
+    widths: &[usize],
+    options: &TableOptions,
+) -> StringLines {
+    let empty = vec![vec![String::new()]; widths.len()];
+    let separator: Vec<String> = widths
+        .iter()
+        .enumerate()
+        .map(|(column, width)| match options.alignment(column) {
+            Alignment::Left => "-".repeat(*width),
+            Alignment::Right => format!("{}:", "-".repeat(width - 1)),
+            Alignment::Center => format!(":{}:", "-".repeat(width - 2)),
+        })
+        .collect();
+    let mut result = grid_row(header.unwrap_or(&empty), widths, options, '|');
+
+    result.push(format!("| {} |", separator.join(" | ")));
+    for row in rows.iter() {
+        result.extend(grid_row(row, widths, options, '|'));
+    }
+
+    result
+}
+
+/************************************************************************************************/
+
+/// Replaces the box-drawing characters of a line by their ASCII equivalents.
+fn to_ascii(line: &str) -> String {
+    line.trim()
+        .chars()
+        .map(|c| match c {
+            '┌' | '┬' | '┐' | '├' | '┼' | '┤' | '└' | '┴' | '┘' | '╞' | '╪' | '╡' => {
+                '+'
+            }
+            '─' => '-',
+            '═' => '=',
+            '│' => '|',
+            c => c,
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Returns `true` when a line is a horizontal line of a grid table.
+fn is_rule(line: &str) -> bool {
+    let line = to_ascii(line);
+
+    line.len() > 2
+        && line.starts_with('+')
+        && line.ends_with('+')
+        && line.contains(['-', '='])
+        && line.chars().all(|c| matches!(c, '+' | '-' | '='))
+}
+
+/************************************************************************************************/
+
+/// Returns the cells of a line of a grid table, cut at the display columns of the borders.
+fn grid_cells(line: &str, borders: &[usize]) -> Vec<String> {
+    let mut cells = vec![String::new(); borders.len().saturating_sub(1)];
+    let mut column = 0;
+
+    for grapheme in line.graphemes(true) {
+        if let Some(cell) = borders
+            .windows(2)
+            .position(|b| b[0] < column && column < b[1])
+        {
+            cells[cell].push_str(grapheme);
+        }
+        column += display_width(grapheme);
+    }
+
+    cells.iter().map(|cell| String::from(cell.trim())).collect()
+}
+
+/************************************************************************************************/
+
+/// Returns the cells of a row made of lines, the lines of a cell are joined with newlines.
+fn join_cells(lines: &[Vec<String>], columns: usize) -> Vec<String> {
+    (0..columns)
+        .map(|column| {
+            let cell: Vec<&str> = lines.iter().map(|line| line[column].as_str()).collect();
+            let used = cell
+                .iter()
+                .rposition(|line| !line.is_empty())
+                .map_or(0, |i| i + 1);
+            cell[..used].join("\n")
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Parses the grid table that starts at a given line.
+fn parse_grid(lines: &[String], start: usize) -> Result<TableBlock, TableError> {
+    let top = to_ascii(&lines[start]);
+    let indent = lines[start].len() - lines[start].trim_start().len();
+    let borders: Vec<usize> = top
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| *c == '+')
+        .map(|(column, _)| column)
+        .collect();
+    let columns = borders.len() - 1;
+    let style = if lines[start].trim_start().starts_with('+') {
+        TableStyle::Ascii
+    } else {
+        TableStyle::Unicode
+    };
+    let mut segments: Vec<Vec<Vec<String>>> = vec![Vec::new()];
+    let mut has_header = false;
+
+    for (index, line) in lines.iter().enumerate().skip(start + 1) {
+        let ascii = to_ascii(line);
+
+        if is_rule(line) && ascii.len() == top.len() {
+            if ascii.contains('=') && segments.len() == 1 {
+                has_header = true;
+            }
+            let is_bottom = lines.get(index + 1).is_none_or(|next| {
+                let bottom_follows = has_header
+                    && segments.len() == 1
+                    && is_rule(next)
+                    && to_ascii(next).len() == top.len();
+                !next.trim_start().starts_with(['|', '│']) && !bottom_follows
+            });
+            if is_bottom {
+                return Ok(TableBlock {
+                    range: start..index + 1,
+                    style,
+                    alignments: Vec::new(),
+                    table: grid_table(segments, has_header, columns),
+                });
+            }
+            segments.push(Vec::new());
+        } else if ascii.starts_with('|') && ascii.ends_with('|') && ascii.len() > 1 {
+            let content = line.as_str().get(indent..).unwrap_or("");
+            if let Some(segment) = segments.last_mut() {
+                segment.push(grid_cells(content, &borders));
+            }
+        } else {
+            return Err(TableError::new(index + 1, "expected a row or a border"));
+        }
+    }
+
+    Err(TableError::new(start + 1, "unterminated table"))
+}
+
+/************************************************************************************************/
+
+/// Returns the table made of the lines between the horizontal lines of a grid table, every
+/// segment of lines is a row.
+fn grid_table(mut segments: Vec<Vec<Vec<String>>>, has_header: bool, columns: usize) -> Table {
+    let header = if has_header {
+        Some(join_cells(&segments.remove(0), columns))
+    } else {
+        None
+    };
+    let rows = segments
+        .iter()
+        .filter(|lines| !lines.is_empty())
+        .map(|lines| join_cells(lines, columns))
+        .collect();
+
+    Table { header, rows }
+}
+
+/************************************************************************************************/
+
+/// Returns the cells of a row of a Markdown table. Escaped pipes are unescaped and `<br>` is
+/// replaced by a newline.
+fn markdown_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let mut cells = vec![String::new()];
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().expect("a cell").push('|');
+                chars.next();
+            }
+            '|' => cells.push(String::new()),
+            c => cells.last_mut().expect("a cell").push(c),
+        }
+    }
+    if cells.len() > 1 && cells.last().is_some_and(|cell| cell.trim().is_empty()) {
+        cells.pop();
+    }
+
+    cells
+        .iter()
+        .map(|cell| cell.trim().replace("<br>", "\n"))
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Returns the alignments of a Markdown separator row, or `None` when the line is not one.
+fn markdown_alignments(line: &str) -> Option<Vec<Alignment>> {
+    if !line.contains('|') && !line.trim_start().starts_with(['-', ':']) {
+        return None;
+    }
+
+    markdown_cells(line)
+        .iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+                return None;
+            }
+            match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => Some(Alignment::Center),
+                (false, true) => Some(Alignment::Right),
+                _ => Some(Alignment::Left),
+            }
+        })
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Returns `true` when a Markdown table starts at a given line.
+fn is_markdown_start(lines: &[String], index: usize) -> bool {
+    lines[index].contains('|')
+        && lines
+            .get(index + 1)
+            .is_some_and(|line| line.contains('|') && markdown_alignments(line).is_some())
+}
+
+/************************************************************************************************/
+
+/// Parses the Markdown table that starts at a given line.
+fn parse_markdown(lines: &[String], start: usize) -> Result<TableBlock, TableError> {
+    let header = markdown_cells(&lines[start]);
+    let alignments = lines
+        .get(start + 1)
+        .and_then(|line| markdown_alignments(line))
+        .ok_or_else(|| TableError::new(start + 2, "missing the separator row"))?;
+
+    if alignments.len() != header.len() {
+        return Err(TableError::new(
+            start + 2,
+            &format!(
+                "the separator row has {} cells, the header row has {}",
+                alignments.len(),
+                header.len()
+            ),
+        ));
+    }
+
+    let end = lines
+        .iter()
+        .enumerate()
+        .skip(start + 2)
+        .find(|(_, line)| line.trim().is_empty() || !line.contains('|'))
+        .map_or(lines.len(), |(index, _)| index);
+
+    Ok(TableBlock {
+        range: start..end,
+        style: TableStyle::Markdown,
+        alignments,
+        table: Table {
+            header: Some(header).filter(|header| header.iter().any(|cell| !cell.is_empty())),
+            rows: lines[start + 2..end]
+                .iter()
+                .map(|line| markdown_cells(line))
+                .collect(),
+        },
+    })
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
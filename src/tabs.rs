@@ -0,0 +1,364 @@
+//! `Tabs` is a module that contains traits to expand tabs into spaces and the other way around.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use std::fmt;
+use std::str::FromStr;
+use unicode_width::UnicodeWidthChar;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The positions of the tab stops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TabStops {
+    /// A tab stop every given amount of columns.
+    Uniform(usize),
+    /// Tab stops on the given (ascending) columns, like `expand -t 4,8,12`. A tab after the last
+    /// tab stop is treated as a single space.
+    List(Vec<usize>),
+}
+
+/************************************************************************************************/
+
+/// The error returned when parsing `TabStops` fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTabStopsError {
+    text: String,
+}
+
+/************************************************************************************************/
+
+/// The options used when expanding or unexpanding tabs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabOptions {
+    /// The positions of the tab stops.
+    pub stops: TabStops,
+    /// Only converts the whitespace at the beginning of a line when `true`.
+    pub leading_only: bool,
+}
+
+/************************************************************************************************/
+
+/// The `Tabs` trait contains all functions to be implemented that convert between tabs and
+/// spaces.
+pub trait Tabs {
+    /// Replaces tabs by the amount of spaces needed to reach the next tab stop. Wide and
+    /// combining characters are taken into account when computing the columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for expanding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::tabs::*;
+    ///
+    /// let s = String::from("a\tb\n\tc\td").expand_tabs(&TabOptions::new(TabStops::Uniform(4)));
+    ///
+    /// assert_eq!(s, "a   b\n    c   d");
+    /// ```
+    fn expand_tabs(self, options: &TabOptions) -> Self;
+
+    /// Replaces runs of spaces that end on a tab stop by tabs. A single space before a tab stop
+    /// is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for unexpanding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::tabs::*;
+    ///
+    /// let s = String::from("        a   b").unexpand_tabs(&TabOptions::new(TabStops::Uniform(4)));
+    ///
+    /// assert_eq!(s, "\t\ta\tb");
+    /// ```
+    fn unexpand_tabs(self, options: &TabOptions) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl TabStops {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns the first tab stop after a given column, or `None` when there are no more tab
+    /// stops.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - the (zero based) column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::tabs::*;
+    ///
+    /// assert_eq!(TabStops::Uniform(8).next_stop(3), Some(8));
+    /// assert_eq!(TabStops::List(vec![2, 6]).next_stop(2), Some(6));
+    /// assert_eq!(TabStops::List(vec![2, 6]).next_stop(6), None);
+    /// ```
+    pub fn next_stop(&self, column: usize) -> Option<usize> {
+        match self {
+            TabStops::Uniform(width) => {
+                let width = (*width).max(1);
+                Some((column / width + 1) * width)
+            }
+            TabStops::List(stops) => stops.iter().copied().find(|stop| *stop > column),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Returns `true` when there is a tab stop on a given column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - the (zero based) column.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn is_stop(&self, column: usize) -> bool {
+        match self {
+            TabStops::Uniform(width) => column > 0 && column % (*width).max(1) == 0,
+            TabStops::List(stops) => stops.contains(&column),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl FromStr for TabStops {
+    type Err = ParseTabStopsError;
+
+    /// Parses tab stops like `expand -t`: a single number for uniform tab stops or a comma or
+    /// blank separated list of ascending columns.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseTabStopsError {
+            text: String::from(s),
+        };
+        let mut stops = Vec::new();
+
+        for part in s.split(|c: char| c == ',' || c.is_whitespace()) {
+            if part.is_empty() {
+                continue;
+            }
+            let stop = part.parse::<usize>().map_err(|_| error())?;
+            if stop == 0 || stops.last().is_some_and(|last| *last >= stop) {
+                return Err(error());
+            }
+            stops.push(stop);
+        }
+
+        match stops.len() {
+            0 => Err(error()),
+            1 => Ok(TabStops::Uniform(stops[0])),
+            _ => Ok(TabStops::List(stops)),
+        }
+    }
+}
+
+/************************************************************************************************/
+
+impl fmt::Display for ParseTabStopsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid tab stops: '{}'", self.text)
+    }
+}
+
+/************************************************************************************************/
+
+impl std::error::Error for ParseTabStopsError {}
+
+/************************************************************************************************/
+
+impl TabOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options for the given tab stops. By default all whitespace in a line
+    /// is converted.
+    ///
+    /// # Arguments
+    ///
+    /// * `stops` - the positions of the tab stops.
+    pub fn new(stops: TabStops) -> Self {
+        TabOptions {
+            stops,
+            leading_only: false,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether only the whitespace at the beginning of a line is converted.
+    pub fn leading_only(mut self, leading_only: bool) -> Self {
+        self.leading_only = leading_only;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Tabs for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn expand_tabs(self, options: &TabOptions) -> Self {
+        self.iter().map(|line| expand_line(line, options)).collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn unexpand_tabs(self, options: &TabOptions) -> Self {
+        self.iter()
+            .map(|line| unexpand_line(line, options))
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Tabs for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn expand_tabs(self, options: &TabOptions) -> Self {
+        self.split().expand_tabs(options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn unexpand_tabs(self, options: &TabOptions) -> Self {
+        self.split().unexpand_tabs(options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns the display column of a byte index in a line, taking tabs and wide and combining
+/// characters into account.
+///
+/// # Arguments
+///
+/// * `line` - the line.
+/// * `index` - the byte index in the line.
+/// * `stops` - the positions of the tab stops.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::tabs::*;
+///
+/// assert_eq!(display_column("\tx", 1, &TabStops::Uniform(8)), 8);
+/// assert_eq!(display_column("日本\tx", 7, &TabStops::Uniform(8)), 8);
+/// ```
+pub fn display_column(line: &str, index: usize, stops: &TabStops) -> usize {
+    line.char_indices()
+        .take_while(|(i, _)| *i < index)
+        .fold(0, |column, (_, c)| advance(column, c, stops))
+}
+
+/************************************************************************************************/
+
+fn advance(column: usize, c: char, stops: &TabStops) -> usize {
+    if c == '\t' {
+        stops.next_stop(column).unwrap_or(column + 1)
+    } else {
+        column + c.width().unwrap_or(0)
+    }
+}
+
+/************************************************************************************************/
+
+fn expand_line(line: &str, options: &TabOptions) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut column = 0;
+    let mut leading = true;
+
+    for c in line.chars() {
+        leading = leading && (c == ' ' || c == '\t');
+        let next = advance(column, c, &options.stops);
+
+        if c == '\t' && (leading || !options.leading_only) {
+            result.push_str(&" ".repeat(next - column));
+        } else {
+            result.push(c);
+        }
+
+        column = next;
+    }
+
+    result
+}
+
+/************************************************************************************************/
+
+fn unexpand_line(line: &str, options: &TabOptions) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut pending = String::new();
+    let mut column = 0;
+    let mut leading = true;
+
+    for c in line.chars() {
+        leading = leading && (c == ' ' || c == '\t');
+
+        if !leading && options.leading_only {
+            result.push_str(&pending);
+            pending.clear();
+            result.push(c);
+            continue;
+        }
+
+        let previous = column;
+        column = advance(column, c, &options.stops);
+
+        match c {
+            '\t' => {
+                let start = previous - pending.len();
+                if advance(start, '\t', &options.stops) < column {
+                    result.push_str(&pending);
+                }
+                pending.clear();
+                result.push('\t');
+            }
+            ' ' => {
+                pending.push(' ');
+                if options.stops.is_stop(column) {
+                    if pending.len() > 1 {
+                        result.push('\t');
+                    } else {
+                        result.push(' ');
+                    }
+                    pending.clear();
+                }
+            }
+            _ => {
+                result.push_str(&pending);
+                pending.clear();
+                result.push(c);
+            }
+        }
+    }
+
+    result.push_str(&pending);
+    result
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,161 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::address::*;
+use crate::tests::helper::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+fn error(column: usize, message: &str) -> AddressError {
+    AddressError {
+        column,
+        message: String::from(message),
+    }
+}
+
+/************************************************************************************************/
+
+#[test]
+fn parse_address_test() {
+    let range: AddressRange = "/a\\/b/+2;'x-".parse().unwrap();
+
+    assert_eq!(
+        range.first,
+        Address {
+            base: Base::Search {
+                pattern: String::from("a/b"),
+                forward: true
+            },
+            offset: 2,
+            column: 1
+        }
+    );
+    assert_eq!(
+        range.last,
+        Address {
+            base: Base::Mark('x'),
+            offset: -1,
+            column: 10
+        }
+    );
+    assert!(range.set_current);
+
+    let range: AddressRange = " ++ -3".parse().unwrap();
+    assert_eq!(range.first.base, Base::Current);
+    assert_eq!(range.first.offset, -1);
+    assert_eq!(range.first, range.last);
+
+    assert_eq!(
+        "1,2x".parse::<AddressRange>(),
+        Err(error(4, "unexpected 'x'"))
+    );
+    assert_eq!("".parse::<AddressRange>(), Err(error(1, "missing address")));
+    assert_eq!(
+        "'1".parse::<AddressRange>(),
+        Err(error(2, "expected the name of a mark"))
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn resolve_address_test() {
+    let s = create_string();
+    let lines = create_string_lines();
+    let context = Context::new(4).mark('a', 7);
+
+    assert_eq!(s.resolve_address("3", &context), Ok(2..3));
+    assert_eq!(s.resolve_address("0", &context), Ok(0..0));
+    assert_eq!(s.resolve_address(".", &context), Ok(4..5));
+    assert_eq!(s.resolve_address("-2", &context), Ok(2..3));
+    assert_eq!(s.resolve_address("$-1,$", &context), Ok(8..10));
+    assert_eq!(s.resolve_address(",", &context), Ok(0..10));
+    assert_eq!(s.resolve_address("%", &context), Ok(0..10));
+    assert_eq!(s.resolve_address(";", &context), Ok(4..10));
+    assert_eq!(s.resolve_address(",3", &context), Ok(0..3));
+    assert_eq!(s.resolve_address("6,", &context), Ok(5..6));
+    assert_eq!(s.resolve_address(".,'a", &context), Ok(4..8));
+    assert_eq!(lines.resolve_address("2;+2", &context), Ok(1..4));
+    assert_eq!(lines.resolve_address("2,+2", &context), Ok(1..7));
+
+    assert_eq!(s.resolve_address("/line[0-5]/", &context), Ok(5..6));
+    assert_eq!(s.resolve_address("/line[0-3]/", &context), Ok(0..1));
+    assert_eq!(s.resolve_address("?line[5-9]?", &context), Ok(9..10));
+    assert_eq!(s.resolve_address("?4", &context), Ok(4..5));
+    assert_eq!(
+        s.resolve_address("//", &context.clone().last_pattern("7")),
+        Ok(7..8)
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn resolve_address_errors_test() {
+    let s = create_string();
+    let context = Context::new(0);
+
+    assert_eq!(
+        s.resolve_address("1,11", &context),
+        Err(error(3, "line 11 is out of range"))
+    );
+    assert_eq!(
+        s.resolve_address(".-2", &context),
+        Err(error(1, "line -1 is out of range"))
+    );
+    assert_eq!(
+        s.resolve_address("5,3", &context),
+        Err(error(3, "the first address is after the last address"))
+    );
+    assert_eq!(
+        s.resolve_address("2,'b", &context),
+        Err(error(3, "mark 'b' is not set"))
+    );
+    assert_eq!(
+        s.resolve_address(" /x/", &context),
+        Err(error(2, "no match for 'x'"))
+    );
+    assert_eq!(
+        s.resolve_address("/(/", &context),
+        Err(error(1, "invalid pattern '('"))
+    );
+    assert_eq!(
+        s.resolve_address("??", &context),
+        Err(error(1, "no previous pattern"))
+    );
+    assert!(String::new().resolve_address(".", &context).is_err());
+    assert_eq!(String::new().resolve_address("$", &context), Ok(0..0));
+}
+
+/************************************************************************************************/
+
+#[test]
+fn resolve_address_overflow_test() {
+    let s = create_string();
+    let context = Context::new(0);
+    let max = isize::MAX;
+
+    assert_eq!(
+        s.resolve_address(&format!("$+{}", max), &context),
+        Err(error(1, "line is out of range"))
+    );
+    assert_eq!(
+        s.resolve_address(&format!("1+{}+1", max), &context),
+        Err(error(max.to_string().len() + 3, "offset is too large"))
+    );
+    assert_eq!(
+        s.resolve_address(&format!("1-{}-2", max), &context),
+        Err(error(max.to_string().len() + 3, "offset is too large"))
+    );
+    assert_eq!(
+        s.resolve_address(&format!("1+{}0", max), &context),
+        Err(error(3, "number is too large"))
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
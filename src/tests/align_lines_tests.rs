@@ -0,0 +1,61 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::align::*;
+use crate::fields::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn align_columns_test() {
+    let s = String::from("a,bb,c\nccc,d\n\n日本,e,ffff");
+
+    assert_eq!(
+        s.clone().align_columns(
+            &AlignOptions::new()
+                .delimiter(Delimiter::Char(','))
+                .separator(" | ")
+        ),
+        "a    | bb | c\nccc  | d\n\n日本 | e  | ffff"
+    );
+    assert_eq!(
+        s.align_columns(
+            &AlignOptions::new()
+                .delimiter(Delimiter::Char(','))
+                .alignments(vec![Alignment::Right, Alignment::Center])
+        ),
+        "   a bb c\n ccc d\n\n日本 e  ffff"
+    );
+    assert_eq!(
+        String::from("  x   y\nlong z").align_columns(&AlignOptions::default()),
+        "x    y\nlong z"
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn align_on_test() {
+    let lines = vec![
+        String::from("a: 1"),
+        String::from("bcd   : 2"),
+        String::from("no token"),
+        String::from("é: 3"),
+        String::from("ignored: 4"),
+    ];
+
+    assert_eq!(
+        lines.clone().align_on(":", 0..4),
+        vec!["a   : 1", "bcd : 2", "no token", "é   : 3", "ignored: 4"]
+    );
+    assert_eq!(lines.clone().align_on(":", 3..4)[3], "é : 3");
+    assert_eq!(lines.clone().align_on(":", 7..9), lines);
+    assert_eq!(lines.clone().align_on("", 0..5), lines);
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,101 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::ansi::*;
+use crate::width::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn strip_ansi_test() {
+    assert_eq!(strip_ansi(""), "");
+    assert_eq!(strip_ansi("plain"), "plain");
+    assert_eq!(strip_ansi("\x1b[2K\x1b[1Gprogress"), "progress");
+    assert_eq!(strip_ansi("\x1b]0;title\x1b\\text"), "text");
+    assert_eq!(strip_ansi("\x1b(Bcharset\x1b7"), "charset");
+    assert_eq!(strip_ansi("cut \x1b[3"), "cut ");
+    assert_eq!(
+        vec![String::from("\x1b[7mx\x1b[27m"), String::from("y")].strip_ansi(),
+        vec!["x", "y"]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn visible_width_test() {
+    assert_eq!(visible_width("\x1b[31m\x1b[0m"), 0);
+    assert_eq!(
+        visible_width("\x1b]8;;https://example.com\x1b\\日本\x1b]8;;\x1b\\"),
+        4
+    );
+    assert_eq!(
+        vec![String::from("ab"), String::from("\x1b[1mabc")].max_visible_width(),
+        3
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn wrap_ansi_test() {
+    assert_eq!(wrap_ansi("", 3), vec![""]);
+    assert_eq!(wrap_ansi("abcde", 2), vec!["ab", "cd", "e"]);
+    assert_eq!(wrap_ansi("日本", 1), vec!["日", "本"]);
+    assert_eq!(
+        wrap_ansi("\x1b[1mab\x1b[22m\x1b[0;4mcd", 2),
+        vec!["\x1b[1mab\x1b[22m\x1b[0;4m\x1b[0m", "\x1b[4mcd\x1b[0m"]
+    );
+    assert_eq!(
+        wrap_ansi("\x1b[1;38;5;9mab\x1b[22mcd\x1b[39mef", 2),
+        vec![
+            "\x1b[1;38;5;9mab\x1b[22m\x1b[0m",
+            "\x1b[38;5;9mcd\x1b[39m",
+            "ef"
+        ]
+    );
+    assert_eq!(
+        wrap_ansi("\x1b[4;7mab\x1b[24;41mcd", 2),
+        vec!["\x1b[4;7mab\x1b[24;41m\x1b[0m", "\x1b[7;41mcd\x1b[0m"]
+    );
+    assert_eq!(
+        String::from("\x1b[31mabc\nd").wrap_ansi(2),
+        "\x1b[31mab\x1b[0m\n\x1b[31mc\x1b[0m\nd"
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn truncate_ansi_test() {
+    let options = WidthOptions::new();
+
+    assert_eq!(
+        truncate_ansi("\x1b[1mabc\x1b[0m", 3, &options),
+        "\x1b[1mabc\x1b[0m"
+    );
+    assert_eq!(
+        truncate_ansi("\x1b[1mabcd", 3, &options),
+        "\x1b[1mab…\x1b[0m"
+    );
+    assert_eq!(
+        truncate_ansi(
+            "a\x1b[32mbc\x1b[0md",
+            3,
+            &options.clone().position(EllipsisPosition::Start)
+        ),
+        "…\x1b[32mc\x1b[0md"
+    );
+    assert_eq!(truncate_ansi("abc", 0, &options), "");
+    assert_eq!(
+        String::from("日本語\nok").truncate_ansi(4, &options.ellipsis("")),
+        "日本\nok"
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,152 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::diff::*;
+use crate::lines::*;
+use crate::tests::helper::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn json_round_trip_test() {
+    let old = create_string();
+    let new = create_string()
+        .remove(2)
+        .set(5, String::from("say \"hi\"\t\\ 日本 \u{1}"))
+        .append(String::from("lineB"));
+    let patch = old.diff(&new).to_patch();
+
+    assert_eq!(patch.changes.len(), 3);
+    assert_eq!(
+        patch.changes[0],
+        Change {
+            old: 2..3,
+            deleted: Some(vec![String::from("line2")]),
+            inserted: Vec::new(),
+        }
+    );
+
+    let json = patch.to_json();
+    assert!(json.contains(r#""inserted": ["say \"hi\"\t\\ 日本 \u0001"]"#));
+    assert_eq!(Patch::from_json(&json), Ok(patch.clone()));
+    assert_eq!(old.clone().apply_patch(&patch), Ok(new.clone()));
+    assert_eq!(old.split().apply_patch(&patch), Ok(new.split()));
+
+    let json = r#"{"changes": [{"old": [0, 1], "inserted": ["😀"], "x": [true, null]}],
+        "version": 1}"#;
+    let patch = Patch::from_json(json).unwrap();
+    assert_eq!(
+        patch.changes,
+        vec![Change::new(0..1, vec![String::from("😀")])]
+    );
+
+    let empty = Patch::new(Vec::new());
+    assert_eq!(Patch::from_json(&empty.to_json()), Ok(empty));
+}
+
+/************************************************************************************************/
+
+#[test]
+fn ed_round_trip_test() {
+    let old = create_string();
+    let new = create_string()
+        .insert(0, String::from("."))
+        .set(4, String::from("lineA"))
+        .insert(5, String::from("."))
+        .insert(6, String::from("lineB"))
+        .remove(9);
+    let patch = old.diff(&new).to_patch();
+    let ed = patch.to_ed();
+
+    assert_eq!(
+        ed,
+        "7d\n4c\nlineA\n..\n.\ns/.//\n5a\nlineB\n.\n0a\n..\n.\ns/.//"
+    );
+
+    let parsed = Patch::from_ed(&ed).unwrap();
+    assert_eq!(parsed.changes.len(), 3);
+    assert!(parsed.changes.iter().all(|change| change.deleted.is_none()));
+    assert_eq!(old.clone().apply_patch(&parsed), Ok(new.clone()));
+    assert_eq!(Patch::from_ed(""), Ok(Patch::new(Vec::new())));
+}
+
+/************************************************************************************************/
+
+#[test]
+fn ignore_options_patch_test() {
+    let old = String::from("a\nHello  World\nc");
+    let new = String::from("a\nhello world\nd");
+    let options = DiffOptions::new().ignore_whitespace(true).ignore_case(true);
+    let patch = old.diff_with(&new, &options).to_patch();
+
+    assert_eq!(patch.changes.len(), 1);
+    assert_eq!(patch.changes[0].old, 1..3);
+    assert_eq!(old.clone().split().apply_patch(&patch), Ok(new.split()));
+    assert_eq!(
+        old.split()
+            .apply_patch(&Patch::from_ed(&patch.to_ed()).unwrap()),
+        Ok(new.split())
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn patch_errors_test() {
+    let patch = create_string().diff(&String::from("line0")).to_patch();
+
+    assert_eq!(
+        String::from("line0\nline1").apply_patch(&patch),
+        Err(PatchError::OutOfRange { line: 10 })
+    );
+    assert_eq!(
+        create_string()
+            .set(4, String::from("x"))
+            .apply_patch(&patch),
+        Err(PatchError::Mismatch { line: 5 })
+    );
+
+    assert_eq!(
+        Patch::from_ed("2d\n3d"),
+        Err(PatchError::Parse {
+            line: 2,
+            message: String::from("commands are not ordered from the end to the start")
+        })
+    );
+    assert_eq!(
+        Patch::from_ed("1a\nx").unwrap_err().to_string(),
+        "invalid patch on line 1: missing end of text"
+    );
+    assert!(Patch::from_ed("1x").is_err());
+    assert!(Patch::from_ed("0d").is_err());
+    assert!(Patch::from_ed("s/.//").is_err());
+
+    assert_eq!(
+        Patch::from_json("{\n  \"version\": 1,\n  \"changes\": [,]\n}"),
+        Err(PatchError::Parse {
+            line: 3,
+            message: String::from("expected a value")
+        })
+    );
+    assert_eq!(
+        Patch::from_json(r#"{"version": 2, "changes": []}"#)
+            .unwrap_err()
+            .to_string(),
+        "invalid patch: unsupported version"
+    );
+    assert!(Patch::from_json(r#"{"version": 1, "changes": [{"old": [2, 1]}]}"#).is_err());
+    assert!(
+        Patch::from_json(r#"{"version": 1, "changes": [{"old": [2, 3]}, {"old": [1, 2]}]}"#)
+            .is_err()
+    );
+    assert!(
+        Patch::from_json(r#"{"version": 1, "changes": [{"old": [0, 1], "deleted": []}]}"#).is_err()
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
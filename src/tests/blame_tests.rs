@@ -0,0 +1,125 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::blame::*;
+use crate::diff::*;
+use crate::lines::*;
+use crate::tests::helper::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+fn origins(lines: &[BlameLine]) -> Vec<(usize, usize)> {
+    lines
+        .iter()
+        .map(|line| (line.revision, line.line))
+        .collect()
+}
+
+/************************************************************************************************/
+
+#[test]
+fn blame_test() {
+    let revisions = vec![
+        Revision::new("r0", &create_string()),
+        Revision::new(
+            "r1",
+            &create_string().remove(2).set(5, String::from("lineA")),
+        ),
+        Revision::new(
+            "r2",
+            &create_string()
+                .remove(2)
+                .set(5, String::from("lineA"))
+                .insert(0, String::from("lineB"))
+                .remove(9),
+        ),
+    ];
+    let lines = blame(&revisions);
+
+    assert_eq!(
+        lines
+            .iter()
+            .map(|line| line.text.clone())
+            .collect::<StringLines>(),
+        revisions[2].text.split()
+    );
+    assert_eq!(
+        origins(&lines),
+        vec![
+            (2, 0),
+            (0, 0),
+            (0, 1),
+            (0, 3),
+            (0, 4),
+            (0, 5),
+            (1, 5),
+            (0, 7),
+            (0, 8)
+        ]
+    );
+
+    assert!(blame(&[]).is_empty());
+    assert_eq!(
+        origins(&blame(&revisions[0..1])),
+        (0..10)
+            .map(|line| (0, line))
+            .collect::<Vec<(usize, usize)>>()
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn blame_moves_test() {
+    let revisions = vec![
+        Revision::new("r0", "[a]\nx=1\ny=2\n[b]\nz=3\nw=4"),
+        Revision::new("r1", "[a]\nx=1\ny=5\n[b]\nz=3\nw=4"),
+        Revision::new("r2", "[b]\nz=3\nw=4\n[a]\nx=1\ny=5"),
+    ];
+
+    assert_eq!(
+        origins(&blame(&revisions)),
+        vec![(0, 3), (0, 4), (0, 5), (2, 3), (2, 4), (2, 5)]
+    );
+
+    let options = BlameOptions::new().moves(Some(MoveOptions::new()));
+    let lines = blame_with(&revisions, &options);
+    assert_eq!(
+        origins(&lines),
+        vec![(0, 3), (0, 4), (0, 5), (0, 0), (0, 1), (1, 2)]
+    );
+    assert_eq!(
+        render_blame(&revisions, &lines),
+        vec![
+            "r0 4 | [b]",
+            "r0 5 | z=3",
+            "r0 6 | w=4",
+            "r0 1 | [a]",
+            "r0 2 | x=1",
+            "r1 3 | y=5"
+        ]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_blame_wide_ids_test() {
+    let revisions = vec![
+        Revision::new("José", "a"),
+        Revision::new("山田", "a\nb"),
+        Revision::new("Bo", "a\nb\nc"),
+    ];
+
+    assert_eq!(
+        render_blame(&revisions, &blame(&revisions)),
+        vec!["José 1 | a", "山田 2 | b", "Bo   3 | c"]
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
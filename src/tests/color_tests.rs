@@ -0,0 +1,213 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::color::*;
+use crate::diagnostics::*;
+use crate::diff::*;
+use std::ffi::OsStr;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn style_paint_test() {
+    assert_eq!(Style::new().prefix(), "");
+    assert_eq!(Style::new().fg(Color::Green).paint(""), "");
+    assert_eq!(
+        Style::new().dim().underline().paint("x"),
+        "\x1b[2;4mx\x1b[0m"
+    );
+    assert_eq!(
+        Style::new()
+            .fg(Color::Fixed(208))
+            .bg(Color::Rgb(1, 2, 3))
+            .paint("x"),
+        "\x1b[38;5;208;48;2;1;2;3mx\x1b[0m"
+    );
+    assert_eq!(
+        Style::new().fg(Color::White).bg(Color::Blue).paint("x"),
+        "\x1b[37;44mx\x1b[0m"
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn color_choice_test() {
+    let empty = OsStr::new("");
+    let set = OsStr::new("1");
+    let xterm = OsStr::new("xterm-256color");
+    let dumb = OsStr::new("dumb");
+
+    assert!(ColorChoice::Always.enabled());
+    assert!(!ColorChoice::Never.enabled());
+    assert_eq!(ColorChoice::default(), ColorChoice::Auto);
+
+    assert!(auto_enabled(None, Some(xterm), true));
+    assert!(auto_enabled(Some(empty), None, true));
+    assert!(!auto_enabled(None, Some(xterm), false));
+    assert!(!auto_enabled(Some(set), Some(xterm), true));
+    assert!(!auto_enabled(None, Some(dumb), true));
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_colored_diff_test() {
+    let theme = Theme::new();
+    let old = String::from("same\nlet x = 1;\ngone");
+    let new = String::from("same\nlet y = 1;");
+    let diff = old.diff(&new);
+
+    assert_eq!(diff.render_colored(&Theme::plain()), diff.render());
+    assert_eq!(
+        diff.render_unified_colored(1, &Theme::plain()),
+        diff.render_unified(1)
+    );
+    assert_eq!(
+        diff.render_colored(&theme),
+        vec![
+            String::from("\x1b[2m same\x1b[0m"),
+            String::from(
+                "\x1b[31m-\x1b[0m\x1b[31mlet \x1b[0m\x1b[7;31mx\x1b[0m\x1b[31m = 1;\x1b[0m"
+            ),
+            String::from("\x1b[31m-gone\x1b[0m"),
+            String::from(
+                "\x1b[32m+\x1b[0m\x1b[32mlet \x1b[0m\x1b[7;32my\x1b[0m\x1b[32m = 1;\x1b[0m"
+            ),
+        ]
+    );
+    assert_eq!(
+        diff.render_unified_colored(0, &theme)[0],
+        "\x1b[36m@@ -2,2 +2 @@\x1b[0m"
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_colored_moves_test() {
+    let theme = Theme::new();
+    let old = String::from("a\nb\nc\nd\ne");
+    let new = String::from("b\nc\nd\ne\na");
+    let diff = old
+        .diff(&new)
+        .detect_moves(&MoveOptions::new().min_lines(1));
+
+    assert_eq!(
+        diff.render_colored(&theme),
+        vec![
+            "\x1b[35m<a\x1b[0m",
+            "\x1b[2m b\x1b[0m",
+            "\x1b[2m c\x1b[0m",
+            "\x1b[2m d\x1b[0m",
+            "\x1b[2m e\x1b[0m",
+            "\x1b[35m>a\x1b[0m",
+        ]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_inline_colored_test() {
+    let theme = Theme::new();
+    let old = String::from("keep\nold word\ngone");
+    let new = String::from("keep\nnew word");
+
+    assert_eq!(
+        old.diff(&new)
+            .render_inline_colored(Granularity::Word, &theme),
+        vec![
+            "\x1b[2mkeep\x1b[0m",
+            "\x1b[7;31mold\x1b[0m\x1b[7;32mnew\x1b[0m word",
+            "\x1b[31mgone\x1b[0m",
+        ]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_side_by_side_colored_test() {
+    let theme = Theme::new();
+    let old = String::from("keep\nold word\ngone");
+    let new = String::from("keep\nnew word");
+    let diff = old.diff(&new);
+    let options = SideBySideOptions::new(21).overflow(Overflow::Wrap);
+
+    assert_eq!(
+        diff.render_side_by_side_colored(&options, &Theme::plain()),
+        diff.render_side_by_side(&options)
+    );
+    assert_eq!(
+        diff.render_side_by_side_colored(&options, &theme),
+        vec![
+            "\x1b[2mkeep\x1b[0m        \x1b[2mkeep\x1b[0m",
+            "\x1b[7;31mold\x1b[0m\x1b[31m word\x1b[0m  \x1b[1;34m|\x1b[0m \x1b[7;32mnew\x1b[0m\x1b[32m word\x1b[0m",
+            "\x1b[31mgone\x1b[0m      \x1b[1;34m<\x1b[0m",
+        ]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_colored_diagnostic_test() {
+    let source = "let a = b;\nlet c = a;";
+    let d = Diagnostic::new(Severity::Error, "mismatched types")
+        .file_name("main.rs")
+        .label(Label::primary(19..20, "expected `u8`"))
+        .label(Label::secondary(4..5, "defined here"));
+
+    assert_eq!(d.render_colored(source, &Theme::plain()), d.render(source));
+    assert_eq!(
+        d.render_colored(source, &Theme::new()),
+        [
+            "\x1b[1;31merror\x1b[0m: mismatched types",
+            "\x1b[1;34m -->\x1b[0m main.rs:2:9",
+            "\x1b[1;34m  |\x1b[0m",
+            "\x1b[1;34m1 | \x1b[0mlet a = b;",
+            "\x1b[1;34m  | \x1b[0m\x1b[1;34m    - defined here\x1b[0m",
+            "\x1b[1;34m2 | \x1b[0mlet c = a;",
+            "\x1b[1;34m  | \x1b[0m\x1b[1;31m        ^ expected `u8`\x1b[0m",
+        ]
+        .join("\n")
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_colored_label_styles_test() {
+    let source = "x y z";
+    let d = Diagnostic::new(Severity::Error, "mismatch")
+        .label(Label::primary(0..1, "first"))
+        .label(Label::secondary(4..5, "a ^ b"));
+
+    assert_eq!(
+        d.render_colored(source, &Theme::new()),
+        [
+            "\x1b[1;31merror\x1b[0m: mismatch",
+            "\x1b[1;34m  |\x1b[0m",
+            "\x1b[1;34m1 | \x1b[0mx y z",
+            "\x1b[1;34m  | \x1b[0m\x1b[1;31m^   - a ^ b\x1b[0m",
+            "\x1b[1;34m  | \x1b[0m\x1b[1;31m|\x1b[0m",
+            "\x1b[1;34m  | \x1b[0m\x1b[1;31mfirst\x1b[0m",
+        ]
+        .join("\n")
+    );
+
+    let d = Diagnostic::new(Severity::Error, "mismatch").label(Label::secondary(4..5, "a ^ b"));
+
+    assert_eq!(
+        d.render_colored(source, &Theme::new()).lines().nth(3),
+        Some("\x1b[1;34m  | \x1b[0m\x1b[1;34m    - a ^ b\x1b[0m")
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,143 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::conflicts::*;
+use crate::lines::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+fn create_conflicts() -> String {
+    String::from(
+        "a\n\
+         <<<<<<< HEAD\n\
+         b\n\
+         =======\n\
+         c\n\
+         >>>>>>> feature\n\
+         d\n\
+         <<<<<<<<< ours\n\
+         <<<<<<< inner\n\
+         e\n\
+         =======\n\
+         f\n\
+         >>>>>>> inner\n\
+         ||||||||| base\n\
+         g\n\
+         =========\n\
+         =======\n\
+         h\n\
+         >>>>>>>>> theirs\n\
+         i",
+    )
+}
+
+/************************************************************************************************/
+
+#[test]
+fn conflicts_test() {
+    let conflicts = create_conflicts().conflicts().unwrap();
+
+    assert_eq!(conflicts.len(), 2);
+    assert_eq!(conflicts[0].range, 1..6);
+    assert_eq!(conflicts[0].marker_size, 7);
+    assert_eq!(conflicts[0].ours.label, "HEAD");
+    assert_eq!(conflicts[0].theirs.label, "feature");
+    assert_eq!(conflicts[0].base, None);
+
+    let nested = &conflicts[1];
+    assert_eq!(nested.range, 7..19);
+    assert_eq!(nested.marker_size, 9);
+    assert_eq!(nested.ours.lines.len(), 5);
+    assert_eq!(nested.base.as_ref().unwrap().label, "base");
+    assert_eq!(nested.base.as_ref().unwrap().lines, vec!["g"]);
+    assert_eq!(nested.theirs.lines, vec!["=======", "h"]);
+
+    let inner = nested.ours.lines.conflicts().unwrap();
+    assert_eq!(inner.len(), 1);
+    assert_eq!(inner[0].ours.lines, vec!["e"]);
+    assert_eq!(inner[0].theirs.lines, vec!["f"]);
+
+    assert!(String::from("a\n=======\nb\n>>>>>>>")
+        .conflicts()
+        .unwrap()
+        .is_empty());
+    assert!(String::from("<<<<<< six\n<<<<<<<x")
+        .conflicts()
+        .unwrap()
+        .is_empty());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn conflicts_errors_test() {
+    assert_eq!(
+        String::from("a\n<<<<<<< ours\nb").conflicts(),
+        Err(ConflictError {
+            line: 2,
+            message: String::from("unterminated conflict")
+        })
+    );
+    assert_eq!(
+        String::from("<<<<<<< ours\nb\n>>>>>>> theirs")
+            .conflicts()
+            .unwrap_err()
+            .to_string(),
+        "line 3: missing '=' marker"
+    );
+    assert!(String::from("<<<<<<<\n<<<<<<<\n=======\n>>>>>>>")
+        .conflicts()
+        .is_err());
+    assert_eq!(
+        create_conflicts().resolve_conflicts(&Resolution::Base),
+        Err(ConflictError {
+            line: 2,
+            message: String::from("the conflict has no base")
+        })
+    );
+    assert!(create_conflicts()
+        .resolve_conflict(2, &Resolution::Ours)
+        .is_err());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn resolve_conflicts_test() {
+    let s = create_conflicts();
+
+    assert_eq!(
+        s.clone().resolve_conflicts(&Resolution::Theirs),
+        Ok(String::from("a\nc\nd\n=======\nh\ni"))
+    );
+    assert_eq!(
+        s.clone()
+            .resolve_conflicts(&Resolution::Ours)
+            .unwrap()
+            .resolve_conflicts(&Resolution::Both),
+        Ok(String::from("a\nb\nd\ne\nf\ni"))
+    );
+    assert_eq!(
+        s.clone()
+            .resolve_conflicts_with(|index, conflict| match index {
+                0 => Resolution::Custom(vec![String::from("x"), String::from("y")]),
+                _ => Resolution::Custom(conflict.base.as_ref().unwrap().lines.clone()),
+            }),
+        Ok(String::from("a\nx\ny\nd\ng\ni"))
+    );
+
+    let s = s.resolve_conflict(1, &Resolution::Base).unwrap();
+    assert_eq!(s.count(), 9);
+    assert_eq!(s.conflicts().unwrap().len(), 1);
+    assert_eq!(
+        s.split().resolve_conflict(0, &Resolution::Ours),
+        Ok(String::from("a\nb\nd\ng\ni").split())
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,117 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::diagnostics::*;
+use crate::lines::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn render_without_labels_test() {
+    let d = Diagnostic::new(Severity::Warning, "file is empty").file_name("empty.ini");
+
+    assert_eq!(d.render(""), "warning: file is empty");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_single_line_labels_test() {
+    let source = "let x = add(one, two);";
+    let d = Diagnostic::new(Severity::Error, "wrong arguments")
+        .label(Label::secondary(8..11, "function"))
+        .label(Label::secondary(12..15, "first"))
+        .label(Label::primary(17..20, "second"));
+
+    let sl = d.render(source).split();
+
+    assert_eq!(sl[0], "error: wrong arguments");
+    assert_eq!(sl[1], "  |");
+    assert_eq!(sl[2], "1 | let x = add(one, two);");
+    assert_eq!(sl[3], "  |         --- ---  ^^^ second");
+    assert_eq!(sl[4], "  |         |   |");
+    assert_eq!(sl[5], "  |         |   first");
+    assert_eq!(sl[6], "  |         |");
+    assert_eq!(sl[7], "  |         function");
+    assert_eq!(sl.len(), 8);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_multi_line_label_test() {
+    let source = "fn main() {\n    run();\n}\n";
+    let d = Diagnostic::new(Severity::Error, "bad block")
+        .file_name("main.rs")
+        .label(Label::primary(10..25, "this block"));
+
+    let sl = d.render(source).split();
+
+    assert_eq!(sl[1], " --> main.rs:1:11");
+    assert_eq!(sl[2], "  |");
+    assert_eq!(sl[3], "1 |   fn main() {");
+    assert_eq!(sl[4], "  |  ___________^");
+    assert_eq!(sl[5], "2 | |     run();");
+    assert_eq!(sl[6], "3 | | }");
+    assert_eq!(sl[7], "  | |_^ this block");
+    assert_eq!(sl.len(), 8);
+
+    let d = Diagnostic::new(Severity::Note, "whole function").label(Label::secondary(0..25, ""));
+    let sl = d.render(source).split();
+
+    assert_eq!(sl[2], "1 | / fn main() {");
+    assert_eq!(sl[3], "2 | |     run();");
+    assert_eq!(sl[4], "3 | | }");
+    assert_eq!(sl[5], "  | |_-");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_separated_lines_test() {
+    let source = "a = 1\nb = 2\nc = 3\nd = 4\ne = 5\nf = 6\ng = 7\nh = 8\ni = 9\nj = 10";
+    let d = Diagnostic::new(Severity::Error, "duplicate key")
+        .label(Label::secondary(0..1, "first defined here"))
+        .label(Label::primary(54..55, "defined again"));
+
+    let sl = d.render(source).split();
+
+    assert_eq!(sl[1], "   |");
+    assert_eq!(sl[2], " 1 | a = 1");
+    assert_eq!(sl[3], "   | - first defined here");
+    assert_eq!(sl[4], "...");
+    assert_eq!(sl[5], "10 | j = 10");
+    assert_eq!(sl[6], "   | ^ defined again");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_tabs_and_wide_characters_test() {
+    let source = "\tname = \"日本\" x";
+    let d = Diagnostic::new(Severity::Help, "here").label(Label::primary(17..18, "x"));
+
+    let sl = d.render(source).split();
+
+    assert_eq!(sl[2], "1 |     name = \"日本\" x");
+    assert_eq!(sl[3], "  |                   ^ x");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn line_column_test() {
+    let source = "line0\nline1\r\nline2";
+
+    assert_eq!(line_column(source, 0), (0, 0));
+    assert_eq!(line_column(source, 8), (1, 2));
+    assert_eq!(line_column(source, 13), (2, 0));
+    assert_eq!(line_column(source, 100), (2, 5));
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
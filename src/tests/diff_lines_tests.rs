@@ -0,0 +1,470 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::diff::*;
+use crate::lines::*;
+use crate::tests::helper::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+fn apply(d: &LineDiff) -> StringLines {
+    let mut result = StringLines::new();
+    let (mut old, mut new) = (0, 0);
+
+    for op in d.ops.iter() {
+        assert_eq!(op.old.start, old);
+        assert_eq!(op.new.start, new);
+        match op.tag {
+            Tag::Equal => {
+                assert_eq!(d.old[op.old.clone()], d.new[op.new.clone()]);
+                result.extend(d.old[op.old.clone()].iter().cloned());
+            }
+            Tag::Delete => assert!(op.new.is_empty()),
+            Tag::Insert => {
+                assert!(op.old.is_empty());
+                result.extend(d.new[op.new.clone()].iter().cloned());
+            }
+        }
+        old = op.old.end;
+        new = op.new.end;
+    }
+
+    assert_eq!(old, d.old.len());
+    assert_eq!(new, d.new.len());
+    result
+}
+
+/************************************************************************************************/
+
+fn lcs(old: &[String], new: &[String]) -> usize {
+    let mut lengths = vec![vec![0; new.len() + 1]; old.len() + 1];
+
+    for (x, a) in old.iter().enumerate() {
+        for (y, b) in new.iter().enumerate() {
+            lengths[x + 1][y + 1] = if a == b {
+                lengths[x][y] + 1
+            } else {
+                lengths[x][y + 1].max(lengths[x + 1][y])
+            };
+        }
+    }
+
+    lengths[old.len()][new.len()]
+}
+
+/************************************************************************************************/
+
+fn changes(d: &LineDiff) -> usize {
+    d.ops
+        .iter()
+        .filter(|op| op.tag != Tag::Equal)
+        .map(|op| op.old.len() + op.new.len())
+        .sum()
+}
+
+/************************************************************************************************/
+
+#[test]
+fn diff_test() {
+    let old = create_string();
+    let new = create_string()
+        .remove(2)
+        .set(5, String::from("lineA"))
+        .append(String::from("lineB"));
+    let d = old.diff(&new);
+
+    assert!(d.has_changes());
+    assert_eq!(apply(&d), new.split());
+    assert_eq!(changes(&d), 4);
+    assert_eq!(
+        d.ops,
+        vec![
+            Op::new(Tag::Equal, 0..2, 0..2),
+            Op::new(Tag::Delete, 2..3, 2..2),
+            Op::new(Tag::Equal, 3..6, 2..5),
+            Op::new(Tag::Delete, 6..7, 5..5),
+            Op::new(Tag::Insert, 7..7, 5..6),
+            Op::new(Tag::Equal, 7..10, 6..9),
+            Op::new(Tag::Insert, 10..10, 9..10),
+        ]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn diff_edge_cases_test() {
+    let empty = String::new();
+    let s = create_string();
+
+    assert!(!empty.diff(&empty).has_changes());
+    assert!(empty.diff(&empty).ops.is_empty());
+    assert!(!s.diff(&s).has_changes());
+
+    let d = empty.diff(&s);
+    assert_eq!(d.ops, vec![Op::new(Tag::Insert, 0..0, 0..10)]);
+
+    let d = s.diff(&empty);
+    assert_eq!(d.ops, vec![Op::new(Tag::Delete, 0..10, 0..0)]);
+
+    let old = String::from("a\nb\nc\na\nb\nb\na");
+    let new = String::from("c\nb\na\nb\na\nc");
+    let d = old.diff(&new);
+    assert_eq!(apply(&d), new.split());
+    assert_eq!(changes(&d), 5);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn diff_minimal_test() {
+    let mut seed: u32 = 7;
+    let mut random = |limit: u32| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (seed >> 16) % limit
+    };
+
+    for _ in 0..200 {
+        let old: StringLines = (0..random(12)).map(|_| random(4).to_string()).collect();
+        let new: StringLines = (0..random(12)).map(|_| random(4).to_string()).collect();
+        let d = old.diff(&new);
+
+        assert_eq!(apply(&d), new);
+        assert_eq!(changes(&d), old.len() + new.len() - 2 * lcs(&old, &new));
+    }
+}
+
+/************************************************************************************************/
+
+#[test]
+fn diff_large_input_test() {
+    let old: StringLines = (0..10_000).map(|index| format!("old {}", index)).collect();
+    let new: StringLines = (0..10_000).map(|index| format!("new {}", index)).collect();
+    let d = old.diff(&new);
+
+    assert_eq!(apply(&d), new);
+    assert_eq!(changes(&d), 20_000);
+
+    let new: StringLines = old
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| index % 100 != 0)
+        .map(|(_, line)| line.clone())
+        .collect();
+    let d = old.diff(&new);
+
+    assert_eq!(apply(&d), new);
+    assert_eq!(changes(&d), 100);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn diff_algorithms_test() {
+    let cases = [
+        (
+            create_string(),
+            create_string().remove(2).insert(7, String::from("lineA")),
+        ),
+        (
+            String::from("a\nb\nc\na\nb\nb\na"),
+            String::from("c\nb\na\nb\na\nc"),
+        ),
+        (String::from("a\n}\nb\n}\n}"), String::from("b\n}\na\n}\nc")),
+        (String::new(), create_string()),
+        (create_string(), String::new()),
+    ];
+
+    for algorithm in [Algorithm::Myers, Algorithm::Patience, Algorithm::Histogram] {
+        let options = DiffOptions::new().algorithm(algorithm);
+        for (old, new) in cases.iter() {
+            let d = old.diff_with(new, &options);
+            assert_eq!(apply(&d), new.split(), "{:?}", algorithm);
+        }
+    }
+
+    let old = String::from("a {\n    x\n}\nb {\n    y\n}\nc {\n    z\n}");
+    let new = String::from("b {\n    y\n}\nc {\n    z\n}\na {\n    x\n}");
+    for algorithm in [Algorithm::Patience, Algorithm::Histogram] {
+        let d = old.diff_with(&new, &DiffOptions::new().algorithm(algorithm));
+        assert_eq!(
+            d.render(),
+            vec![
+                "-a {", "-    x", "-}", " b {", "     y", " }", " c {", "     z", "+}", "+a {",
+                "+    x", " }",
+            ],
+            "{:?}",
+            algorithm
+        );
+    }
+}
+
+/************************************************************************************************/
+
+#[test]
+fn diff_ignore_options_test() {
+    let old = String::from("Line0\nline1  a\n\nline2");
+    let new = String::from("line0\n  line1 a\nline2\n\n");
+
+    let d = old.diff_with(&new, &DiffOptions::new().ignore_whitespace(true));
+    assert_eq!(changes(&d), 4);
+
+    let options = DiffOptions::new().ignore_whitespace(true).ignore_case(true);
+    let d = old.diff_with(&new, &options);
+    assert_eq!(
+        d.ops,
+        vec![
+            Op::new(Tag::Equal, 0..2, 0..2),
+            Op::new(Tag::Delete, 2..3, 2..2),
+            Op::new(Tag::Equal, 3..4, 2..3),
+            Op::new(Tag::Insert, 4..4, 3..4),
+        ]
+    );
+
+    let d = old.diff_with(&new, &options.ignore_blank_lines(true));
+    assert_eq!(
+        d.ops,
+        vec![
+            Op::new(Tag::Equal, 0..2, 0..2),
+            Op::new(Tag::Delete, 2..3, 2..2),
+            Op::new(Tag::Equal, 3..4, 2..3),
+            Op::new(Tag::Insert, 4..4, 3..4),
+        ]
+    );
+    assert!(d.is_ignored(&d.ops[1]));
+    assert!(!d.has_changes());
+    assert_eq!(d.render(), vec![" Line0", " line1  a", " line2"]);
+    assert!(d.render_unified(3).is_empty());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn ignore_blank_lines_patch_test() {
+    let old = String::from("a\n\nb\nc");
+    let new = String::from("a\nb\nC");
+    let d = old.diff_with(&new, &DiffOptions::new().ignore_blank_lines(true));
+
+    assert_eq!(d.render(), vec![" a", " b", "-c", "+C"]);
+    assert_eq!(
+        d.render_unified(3),
+        vec!["@@ -1,4 +1,3 @@", " a", "-", " b", "-c", "+C"]
+    );
+    assert_eq!(
+        d.render_unified(1),
+        vec!["@@ -3,2 +2,2 @@", " b", "-c", "+C"]
+    );
+    assert_eq!(d.render_unified(0), vec!["@@ -4 +3 @@", "-c", "+C"]);
+    assert_eq!(old.clone().apply_patch(&d.to_patch()).unwrap(), new);
+    assert_eq!(
+        old.apply_patch(&Patch::from_ed(&d.to_patch().to_ed()).unwrap())
+            .unwrap(),
+        new
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn detect_moves_test() {
+    let old = String::from("a1\nb2\nc3\nd4\nx\ne\nf");
+    let new = String::from("x\ne\nf\na1\nB2\nc3\nd4");
+    let d = old.diff(&new);
+
+    let moved = d.clone().detect_moves(&MoveOptions::new());
+    assert_eq!(moved, d);
+
+    let moved = d.clone().detect_moves(&MoveOptions::new().min_lines(2));
+    assert_eq!(apply(&moved), new.split());
+    assert_eq!(
+        moved.ops,
+        vec![
+            Op::new(Tag::Delete, 0..2, 0..0),
+            Op::moved(Tag::Delete, 2..4, 0..0, 0),
+            Op::new(Tag::Equal, 4..7, 0..3),
+            Op::new(Tag::Insert, 7..7, 3..5),
+            Op::moved(Tag::Insert, 7..7, 5..7, 0),
+        ]
+    );
+    assert!(moved.ops[1].is_moved_from());
+    assert!(moved.ops[4].is_moved_to());
+
+    let moved = d.detect_moves(&MoveOptions::new().similarity(0.5));
+    assert_eq!(apply(&moved), new.split());
+    assert_eq!(
+        moved.render(),
+        vec!["<a1", "<b2", "<c3", "<d4", " x", " e", " f", ">a1", ">B2", ">c3", ">d4"]
+    );
+    assert_eq!(
+        moved.render_unified(0)[0..3],
+        [
+            String::from("@@ -1,4 +0,0 @@"),
+            String::from("-a1"),
+            String::from("-b2")
+        ]
+    );
+    assert!(moved.paired_lines().is_empty());
+    assert_eq!(
+        moved.render_side_by_side(&SideBySideOptions::new(11).suppress_common(true)),
+        vec![
+            "a1   (",
+            "b2   (",
+            "c3   (",
+            "d4   (",
+            "     ) a1",
+            "     ) B2",
+            "     ) c3",
+            "     ) d4"
+        ]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn detect_moves_large_input_test() {
+    let first: StringLines = (0..2000).map(|i| format!("first {}", i)).collect();
+    let second: StringLines = (0..2500).map(|i| format!("second {}", i)).collect();
+    let old = [first.clone(), second.clone()].concat().merge();
+    let new = [second, first].concat().merge();
+
+    let moved = old.diff(&new).detect_moves(&MoveOptions::new());
+
+    assert_eq!(apply(&moved), new.split());
+    assert_eq!(
+        moved.ops,
+        vec![
+            Op::moved(Tag::Delete, 0..2000, 0..0, 0),
+            Op::new(Tag::Equal, 2000..4500, 0..2500),
+            Op::moved(Tag::Insert, 4500..4500, 2500..4500, 0),
+        ]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_unified_test() {
+    let old = create_string();
+    let new = create_string()
+        .set(1, String::from("lineA"))
+        .set(8, String::from("lineB"));
+    let d = old.diff(&new);
+
+    assert_eq!(
+        d.render_unified(2),
+        vec![
+            "@@ -1,4 +1,4 @@",
+            " line0",
+            "-line1",
+            "+lineA",
+            " line2",
+            " line3",
+            "@@ -7,4 +7,4 @@",
+            " line6",
+            " line7",
+            "-line8",
+            "+lineB",
+            " line9",
+        ]
+    );
+    let sl = d.render_unified(3);
+    assert_eq!(sl.len(), 13);
+    assert_eq!(sl[0], "@@ -1,10 +1,10 @@");
+    assert!(old.diff(&old).render_unified(3).is_empty());
+
+    let d = String::from("a").diff(&String::from("a\nb"));
+    assert_eq!(d.render_unified(0), vec!["@@ -1,0 +2 @@", "+b"]);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn inline_test() {
+    let old = String::from("same\nthe old value\nremoved");
+    let new = String::from("same\nthe new value");
+    let d = old.diff(&new);
+
+    assert_eq!(d.paired_lines(), vec![(1, 1)]);
+
+    let lines = d.inline(Granularity::Word);
+    assert_eq!(lines.len(), 3);
+    assert_eq!((lines[1].old, lines[1].new), (Some(1), Some(1)));
+    assert_eq!(
+        lines[1].tokens,
+        vec![
+            Token::new(Tag::Equal, "the "),
+            Token::new(Tag::Delete, "old"),
+            Token::new(Tag::Insert, "new"),
+            Token::new(Tag::Equal, " value"),
+        ]
+    );
+    assert_eq!((lines[2].old, lines[2].new), (Some(2), None));
+
+    assert_eq!(
+        d.render_inline(Granularity::Char),
+        vec!["same", "the [-old-]{+new+} value", "[-removed-]"]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn diff_words_test() {
+    assert_eq!(
+        render_tokens(&diff_words("fn add(a: u8)", "fn add(a: u16, b: u16)")),
+        "fn add(a: [-u8-]{+u16, b: u16+})"
+    );
+    assert_eq!(render_tokens(&diff_words("", "new")), "{+new+}");
+    assert_eq!(render_tokens(&diff_chars("日本語", "日本")), "日本[-語-]");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_side_by_side_test() {
+    let old = String::from("line0\nline1\nline2");
+    let new = String::from("line0\nline1 changed\nline2");
+    let d = old.diff(&new);
+
+    let options = SideBySideOptions::new(23).line_numbers(true);
+    assert_eq!(
+        d.render_side_by_side(&options),
+        vec![
+            "1 line0      1 line0",
+            "2 line1    | 2 line1 ch",
+            "3 line2      3 line2",
+        ]
+    );
+
+    let options = options.overflow(Overflow::Wrap).suppress_common(true);
+    assert_eq!(
+        d.render_side_by_side(&options),
+        vec!["2 line1    | 2 line1 ch", "               anged"]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_side_by_side_wide_characters_test() {
+    let old = String::from("日本語のテキスト\ta");
+    let new = String::from("x\ta");
+    let d = old.diff(&new);
+
+    let options = SideBySideOptions::new(19);
+    assert_eq!(d.render_side_by_side(&options), vec!["日本語の | x"]);
+
+    let options = options.overflow(Overflow::Wrap);
+    assert_eq!(
+        d.render_side_by_side(&options),
+        vec!["日本語の | x", "テキスト   a", "", "a"]
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,121 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::fields::*;
+use crate::tests::helper::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn fields_test() {
+    fn split<'a>(line: &'a str, delimiter: &Delimiter) -> Vec<&'a str> {
+        fields(line, delimiter).collect()
+    }
+
+    assert_eq!(split("  a \t b  ", &Delimiter::Whitespace), vec!["a", "b"]);
+    assert!(split("   ", &Delimiter::Whitespace).is_empty());
+    assert_eq!(
+        split(",a,,b,", &Delimiter::Char(',')),
+        vec!["", "a", "", "b", ""]
+    );
+    assert_eq!(split("", &Delimiter::Char(',')), vec![""]);
+    assert_eq!(
+        split("a::b:c", &Delimiter::Str(String::from("::"))),
+        vec!["a", "b:c"]
+    );
+}
+
+/************************************************************************************************/
+
+#[cfg(feature = "regex")]
+#[test]
+fn fields_regex_test() {
+    assert_eq!(
+        fields("a1b22c", &Delimiter::regex("[0-9]*").unwrap()).collect::<Vec<_>>(),
+        vec!["a", "b", "c"]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn column_test() {
+    let lines = create_string_lines();
+
+    assert_eq!(lines.column(0, &Delimiter::Char('e'))[3], "lin");
+    assert_eq!(lines.column(1, &Delimiter::Char('e'))[3], "3");
+    assert_eq!(create_string().column(2, &Delimiter::Char('e'))[0], "");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn selection_test() {
+    let selection: Selection = "2,4-5,-1,9-".parse().unwrap();
+
+    assert_eq!(selection.ranges, vec![2..=2, 4..=5, 1..=1, 9..=usize::MAX]);
+    assert!(selection.contains(1) && selection.contains(5) && selection.contains(100));
+    assert!(!selection.contains(3) && !selection.contains(8));
+    assert_eq!(Selection::new().range(3..=4).ranges, vec![3..=4]);
+
+    let error = |s: &str| s.parse::<Selection>().unwrap_err().to_string();
+
+    assert_eq!(error("1,,2"), "column 3: empty item");
+    assert_eq!(error("1,-"), "column 3: empty range");
+    assert_eq!(error("1,3-x"), "column 5: invalid number");
+    assert_eq!(error("0"), "column 1: numbers start at 1");
+    assert_eq!(error("1,5-3"), "column 3: decreasing range");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn cut_fields_test() {
+    let s = String::from("a b  c d\nnone\ne f");
+    let selection: Selection = "3-,1".parse().unwrap();
+
+    assert_eq!(
+        s.clone().cut_fields(&selection, &FieldOptions::default()),
+        "a c d\nnone\ne"
+    );
+    assert_eq!(
+        s.cut_fields(
+            &selection,
+            &FieldOptions::default()
+                .output_delimiter(Some(","))
+                .only_delimited(true)
+        ),
+        "a,c,d\ne"
+    );
+    assert_eq!(
+        String::from("a;b;c").cut_fields(
+            &"2".parse().unwrap(),
+            &FieldOptions::new(Delimiter::Char(';'))
+        ),
+        "b"
+    );
+    assert_eq!(
+        String::from("a->b->c").cut_fields(
+            &"1,3".parse().unwrap(),
+            &FieldOptions::new(Delimiter::Str(String::from("->")))
+        ),
+        "a->c"
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn cut_chars_test() {
+    let selection: Selection = "1,4-".parse().unwrap();
+
+    assert_eq!(create_string_lines().cut_chars(&selection)[7], "le7");
+    assert_eq!(String::from("äbçdé\n").cut_chars(&selection), "ädé");
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,74 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::gutter::*;
+use crate::lines::*;
+use crate::tests::helper::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn number_lines_test() {
+    let s = create_string().number_lines(&GutterOptions::new());
+
+    assert_eq!(s.count(), 10);
+    assert_eq!(s.get(0), " 1 | line0");
+    assert_eq!(s.get(9), "10 | line9");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn separator_test() {
+    let sl = create_string_lines().number_lines(&GutterOptions::new().start(0).separator("\t"));
+
+    assert_eq!(sl[0], "0\tline0");
+    assert_eq!(sl[9], "9\tline9");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn skip_blank_test() {
+    let s = String::from("line0\n  \nline2\n\nline4")
+        .number_lines(&GutterOptions::new().separator(": ").skip_blank(true));
+
+    assert_eq!(s, "1: line0\n :\n2: line2\n :\n3: line4");
+
+    let s = String::from("line0\n  \nline2").number_lines(&GutterOptions::new().separator(": "));
+
+    assert_eq!(s, "1: line0\n2:\n3: line2");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn window_test() {
+    let options = GutterOptions::new().window(8, 2);
+    let sl = create_string_lines().number_lines(&options);
+
+    assert_eq!(
+        sl,
+        vec![" 7 | line6", " 8 | line7", " 9 | line8", "10 | line9"]
+    );
+
+    let options = GutterOptions::new().window(1, 3);
+    let sl = create_string_lines().number_lines(&options);
+
+    assert_eq!(sl.len(), 5);
+    assert_eq!(sl[0], "1 | line0");
+    assert_eq!(sl[4], "5 | line4");
+
+    let options = GutterOptions::new().window(20, 3);
+    assert!(create_string_lines().number_lines(&options).is_empty());
+
+    let options = GutterOptions::new().window(2, usize::MAX);
+    assert_eq!(create_string_lines().number_lines(&options).len(), 10);
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,93 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use crate::logical::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn backslash_test() {
+    let s =
+        String::from("RUN apt-get update && \\\n    apt-get install -y \\\n    curl\nCMD [\"sh\"]");
+    let logical = s.logical_lines(&ContinuationOptions::new());
+
+    assert_eq!(logical.len(), 2);
+    assert_eq!(
+        logical[0].text,
+        "RUN apt-get update &&     apt-get install -y     curl"
+    );
+    assert_eq!(logical[0].range, 0..3);
+    assert_eq!(logical[0].starts, vec![0, 22, 45]);
+    assert_eq!(logical[1].text, "CMD [\"sh\"]");
+    assert_eq!(logical[1].range, 3..4);
+    assert_eq!(logical[1].starts, vec![0]);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn escaped_marker_test() {
+    let sl = vec![
+        String::from("a\\\\"),
+        String::from("b\\\\\\"),
+        String::from("c\\"),
+    ];
+
+    assert_eq!(
+        sl.join_continuations(&ContinuationOptions::new()),
+        vec!["a\\\\", "b\\\\c"]
+    );
+    assert!(StringLines::new()
+        .logical_lines(&ContinuationOptions::new())
+        .is_empty());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn options_test() {
+    let s = String::from("key = one &\n  two\n\n  three");
+
+    assert_eq!(
+        s.clone()
+            .join_continuations(&ContinuationOptions::new().marker("&").separator(" ")),
+        "key = one    two\n\n  three"
+    );
+    assert_eq!(
+        s.clone()
+            .join_continuations(&ContinuationOptions::new().no_marker()),
+        s
+    );
+
+    let s = String::from("Received: from a\n\tby b\n \nBody\n  text");
+    let logical = s.logical_lines(&ContinuationOptions::headers());
+
+    assert_eq!(logical[0].text, "Received: from a\tby b");
+    assert_eq!(logical[1].text, " ");
+    assert_eq!(logical[2].text, "Body  text");
+    assert_eq!(logical[2].range, 3..5);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn position_test() {
+    let s = String::from("x := a -\\\n  b\ny := c");
+    let logical = s.logical_lines(&ContinuationOptions::new().separator(" "));
+
+    assert_eq!(logical[0].text, "x := a -   b");
+    assert_eq!(logical[0].position(0), (0, 0));
+    assert_eq!(logical[0].position(7), (0, 7));
+    assert_eq!(logical[0].position(8), (0, 8));
+    assert_eq!(logical[0].position(9), (1, 0));
+    assert_eq!(logical[0].position(11), (1, 2));
+    assert_eq!(logical[1].position(5), (2, 5));
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -17,6 +17,15 @@ fn merge_test() {
     assert_eq!(sl.merge(), s);
 }
 
+/************************************************************************************************/
+
+#[test]
+fn merge_empty_lines_test() {
+    let sl = vec![String::new(), String::from("line1"), String::new()];
+
+    assert_eq!(sl.merge(), "\nline1\n");
+}
+
 /************************************************************************************************/
 /************************************************************************************************/
 /************************************************************************************************/
@@ -2,10 +2,32 @@
 /************************************************************************************************/
 /************************************************************************************************/
 
+#[cfg(feature = "regex")]
+mod address_lines_tests;
+mod align_lines_tests;
+mod ansi_lines_tests;
+mod apply_patch_tests;
+mod blame_tests;
+mod color_tests;
+mod conflict_lines_tests;
+mod diagnostics_tests;
+mod diff_lines_tests;
+mod field_lines_tests;
+mod gutter_tests;
 mod helper;
 mod lines_for_string_tests;
+mod logical_lines_tests;
 mod merge_lines_tests;
+mod paragraphs_tests;
+#[cfg(feature = "regex")]
+mod sed_lines_tests;
+mod sloc_tests;
 mod split_lines_tests;
+mod table_tests;
+mod tabs_tests;
+mod whitespace_tests;
+mod width_tests;
+mod wrap_lines_tests;
 
 /************************************************************************************************/
 /************************************************************************************************/
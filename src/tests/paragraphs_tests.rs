@@ -0,0 +1,125 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use crate::paragraphs::*;
+use crate::tests::helper::*;
+use crate::wrap::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn paragraphs_test() {
+    let p = String::from("\n\none\ntwo\n \t\nthree\n\n").paragraphs();
+
+    assert_eq!(p.len(), 2);
+    assert_eq!(
+        p.iter()
+            .map(|paragraph| paragraph.range.clone())
+            .collect::<Vec<_>>(),
+        vec![2..4, 5..6]
+    );
+    assert_eq!(p.paragraphs[0].text(), "one\ntwo");
+    assert_eq!(p.separators, vec![vec!["", ""], vec![" \t"], vec![""]]);
+    assert!(p.final_newline);
+    assert_eq!(p.merge(), "\n\none\ntwo\n \t\nthree\n\n");
+
+    let p = create_string_lines().paragraphs();
+
+    assert_eq!(p.len(), 1);
+    assert_eq!(p.separators, vec![StringLines::new(), StringLines::new()]);
+    assert_eq!(p.merge(), create_string());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn empty_paragraphs_test() {
+    for text in ["", "\n", "  \n\n"].iter() {
+        let p = String::from(*text).paragraphs();
+
+        assert!(p.is_empty());
+        assert_eq!(p.separators.len(), 1);
+        assert_eq!(p.merge(), *text);
+    }
+}
+
+/************************************************************************************************/
+
+#[test]
+fn map_paragraphs_test() {
+    let p = String::from("a\n\nb\nc\n\nd")
+        .paragraphs()
+        .map(|paragraph| paragraph.lines.iter().map(|line| line.repeat(2)).collect());
+
+    assert_eq!(p.merge(), "aa\n\nbb\ncc\n\ndd");
+
+    let p = p.map(|paragraph| {
+        if paragraph.lines.len() == 1 {
+            vec![paragraph.lines[0].clone(), String::from("--")]
+        } else {
+            paragraph.lines.clone()
+        }
+    });
+
+    assert_eq!(
+        p.iter()
+            .map(|paragraph| paragraph.range.clone())
+            .collect::<Vec<_>>(),
+        vec![0..2, 3..5, 6..8]
+    );
+    assert_eq!(p.lines(), vec!["aa", "--", "", "bb", "cc", "", "dd", "--"]);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn filter_paragraphs_test() {
+    let text = String::from("\na\n\nb\n \nc\n\n\n");
+
+    assert_eq!(
+        text.paragraphs()
+            .filter(|paragraph| paragraph.text() != "a")
+            .merge(),
+        "\nb\n \nc\n\n\n"
+    );
+    assert_eq!(
+        text.paragraphs()
+            .filter(|paragraph| paragraph.text() != "b")
+            .merge(),
+        "\na\n\nc\n\n\n"
+    );
+    assert_eq!(
+        text.paragraphs()
+            .filter(|paragraph| paragraph.text() != "c")
+            .merge(),
+        "\na\n\nb\n\n\n"
+    );
+
+    let p = text
+        .paragraphs()
+        .filter(|paragraph| paragraph.text() == "c");
+
+    assert_eq!(p.paragraphs[0].range, 1..2);
+    assert_eq!(p.merge(), "\nc\n\n\n");
+    assert!(text.paragraphs().filter(|_| false).is_empty());
+    assert_eq!(text.paragraphs().filter(|_| false).merge(), "\n\n");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn reflow_paragraphs_test() {
+    let text = String::from("one two\nthree four five\n\n\nsix\nseven\n");
+    let p = text.paragraphs().reflow(&WrapOptions::new(13));
+
+    assert_eq!(p.merge(), "one two three\nfour five\n\n\nsix seven\n");
+    assert_eq!(p.paragraphs[1].range, 4..5);
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,140 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::sed::*;
+use crate::tests::helper::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+fn sed(text: &str, script: &str) -> String {
+    String::from(text).sed(&Script::parse(script).unwrap())
+}
+
+/************************************************************************************************/
+
+#[test]
+fn sed_addresses_test() {
+    assert_eq!(
+        create_string().sed(&Script::parse("3,$d").unwrap()),
+        "line0\nline1"
+    );
+    assert_eq!(sed("a\nb\nc\nd", "/b/,/c/d"), "a\nd");
+    assert_eq!(sed("a\nb\nc\nd", "2!d"), "b");
+    assert_eq!(sed("a\nb\nc\nd", "\\,c,,$ s/$/!/"), "a\nb\nc!\nd!");
+    assert_eq!(sed("a\nb\nc", "/x/,/y/d"), "a\nb\nc");
+    assert_eq!(sed("a\nb\nc", "2,1d"), "a\nc");
+    assert_eq!(sed("a\nb\nc\nb", "/b/{s/b/B/;p}"), "a\nB\nB\nc\nB\nB");
+    assert_eq!(
+        sed("a\nb\nc\nd", "/a/,/b/s/$/1/\n/b/,/c/{/b/,/c/s/$/2/}"),
+        "a1\nb12\nc2\nd"
+    );
+
+    let script = Script::parse("1,2p;3p;/x/,${2,3p}").unwrap();
+    let block = match &script.commands[2].kind {
+        CommandKind::Block(commands) => &commands[0],
+        _ => unreachable!(),
+    };
+
+    assert_eq!(script.commands[0].selector.range, 0);
+    assert_eq!(script.commands[2].selector.range, 1);
+    assert_eq!(block.selector.range, 2);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn sed_substitute_test() {
+    assert_eq!(sed("aaa", "s/a/b/"), "baa");
+    assert_eq!(sed("aaa", "s/a/b/g"), "bbb");
+    assert_eq!(sed("aaa", "s/a/b/2g"), "abb");
+    assert_eq!(sed("aAa", "s/A/x/ig"), "xxx");
+    assert_eq!(sed("a b", "s|(\\w) (\\w)|[\\2&\\1]|"), "[ba ba]");
+    assert_eq!(sed("a/b", "s/\\//\\n/"), "a\nb");
+    assert_eq!(sed("ab\nb", "/a/s//x/"), "xb\nb");
+    assert_eq!(sed("a\nb", "#n\ns/a/x/p"), "x");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn sed_text_test() {
+    assert_eq!(sed("a\nb", "1i first\n$a last"), "first\na\nb\nlast");
+    assert_eq!(sed("a\nb\nc\nd", "2,3c\\\nnew\\\ntext"), "a\nnew\ntext\nd");
+    assert_eq!(sed("a\nb\nc", "/[ab]/c x"), "x\nx\nc");
+    assert_eq!(sed("abc", "y/abc/xyz/"), "xyz");
+    assert_eq!(sed("a\nb", "="), "1\na\n2\nb");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn sed_hold_space_test() {
+    assert_eq!(sed("a\nb\nc", "1!G;h;$!d"), "c\nb\na");
+    assert_eq!(sed("a\nb\nc", "x;1d;$G"), "a\nb\nc");
+    assert_eq!(sed("a\nb\nc", "n;d"), "a\nc");
+    assert_eq!(sed("a\nb\nc", "$!N;s/\\n/-/"), "a-b\nc");
+    assert_eq!(sed("a\nb\nc", "2q"), "a\nb");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn sed_stream_test() {
+    let script = Script::parse("s/^/> /").unwrap().quiet(true);
+    let mut output = Vec::new();
+    script.run_stream("a\nb\n".as_bytes(), &mut output).unwrap();
+
+    assert_eq!(output, b"");
+
+    let script = Script::parse("s/^/> /p").unwrap().quiet(true);
+    let mut output = Vec::new();
+    script.run_stream("a\nb\n".as_bytes(), &mut output).unwrap();
+
+    assert_eq!(String::from_utf8(output).unwrap(), "> a\n> b\n");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn sed_parse_errors_test() {
+    let error = |script: &str| Script::parse(script).unwrap_err().to_string();
+
+    assert_eq!(error("s/a/b"), "line 1, column 1: missing '/'");
+    assert_eq!(error("p\n  s/(/x/"), "line 2, column 3: invalid pattern");
+    assert_eq!(
+        error("y/ab/x/"),
+        "line 1, column 1: the lists have different lengths"
+    );
+    assert_eq!(
+        error("1,d"),
+        "line 1, column 3: missing the end of the range"
+    );
+    assert_eq!(error("0p"), "line 1, column 1: invalid line number");
+    assert_eq!(error("{p"), "line 1, column 3: missing '}'");
+    assert_eq!(error("p}"), "line 1, column 2: unexpected '}'");
+    assert_eq!(
+        error("p x"),
+        "line 1, column 3: unexpected 'x' after the command"
+    );
+
+    let script = Script::parse("# comment\n/a/ !{\n  p\n}").unwrap();
+    let command = &script.commands[0];
+
+    assert_eq!((command.line, command.column), (2, 6));
+    assert!(command.selector.negated);
+    assert_eq!(
+        command.selector.first,
+        Some(Match::Pattern(String::from("a")))
+    );
+    match &command.kind {
+        CommandKind::Block(commands) => assert_eq!((commands[0].line, commands[0].column), (3, 3)),
+        kind => panic!("unexpected command {:?}", kind),
+    }
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,220 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use crate::sloc::*;
+use crate::table::*;
+use crate::tests::helper::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn classify_rust_test() {
+    use LineKind::*;
+
+    let s = String::from(
+        "/* outer /* inner */\n\n   still */ fn f() {\nlet s = \"/* no \\\" // comment\";\nlet t = \"multi\n\n  line\"; /* c */\n    // done",
+    );
+
+    assert_eq!(
+        s.classify(&Syntax::preset("rs").unwrap()),
+        vec![Comment, Blank, Mixed, Code, Code, Blank, Mixed, Comment]
+    );
+    assert_eq!(
+        s.classify(&Syntax::preset("c").unwrap()),
+        vec![Comment, Blank, Code, Code, Code, Blank, Mixed, Comment]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn classify_rust_literals_test() {
+    use LineKind::*;
+
+    let rust = Syntax::preset("rs").unwrap();
+
+    assert_eq!(
+        String::from("let q = '\"';\n// c\n").classify(&rust),
+        vec![Code, Comment]
+    );
+    assert_eq!(
+        String::from("let e = '\\'';\nlet u = '\\u{22}'; // c\nfn f<'a>(s: &'a str) {}\n// c")
+            .classify(&rust),
+        vec![Code, Mixed, Code, Comment]
+    );
+    assert_eq!(
+        String::from("'outer: loop { break 'outer; } // c\nlet c = 'x'; /* c */").classify(&rust),
+        vec![Mixed, Mixed]
+    );
+    assert_eq!(
+        String::from("let p = r\"\\\";\n// c\nlet h = r#\"a \"quoted\" // b\n\"#; // c\n// d")
+            .classify(&rust),
+        vec![Code, Comment, Code, Mixed, Comment]
+    );
+    assert_eq!(
+        String::from("let b = br##\"\"# // no\"##;\n// c").classify(&rust),
+        vec![Code, Comment]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn classify_presets_test() {
+    use LineKind::*;
+
+    let python = String::from("x = '''\n# not a comment\n'''  # comment\n    \n#!");
+
+    assert_eq!(
+        python.classify(&Syntax::preset("PY").unwrap()),
+        vec![Code, Code, Mixed, Blank, Comment]
+    );
+
+    let lua = String::from("--[[ block\n]] x = 1 -- c\n-- line");
+
+    assert_eq!(
+        lua.classify(&Syntax::preset("lua").unwrap()),
+        vec![Comment, Mixed, Comment]
+    );
+
+    let html = String::from("<p>\n<!-- a -->\n</p><!--\n-->");
+
+    assert_eq!(
+        html.classify(&Syntax::preset("html").unwrap()),
+        vec![Code, Comment, Mixed, Comment]
+    );
+
+    let ini = String::from("; c\n# c\nkey = value ; no comment");
+
+    assert_eq!(
+        ini.classify(&Syntax::preset("ini").unwrap()),
+        vec![Comment, Comment, Mixed]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn classify_c_like_presets_test() {
+    use LineKind::*;
+
+    let go = String::from("s := `raw \\\n// not a comment\n` // c\n/* c */");
+
+    assert_eq!(
+        go.classify(&Syntax::preset("go").unwrap()),
+        vec![Code, Code, Mixed, Comment]
+    );
+
+    let js = String::from("let t = `a ${b}\n/* not a comment */\n`; // c");
+
+    assert_eq!(
+        js.classify(&Syntax::preset("js").unwrap()),
+        vec![Code, Code, Mixed]
+    );
+    assert_eq!(
+        js.classify(&Syntax::preset("ts").unwrap()),
+        vec![Code, Code, Mixed]
+    );
+
+    let kotlin = String::from("/* outer /* inner */\n*/ val s = \"\"\"\n// no\\\"\"\"");
+
+    assert_eq!(
+        kotlin.classify(&Syntax::preset("kt").unwrap()),
+        vec![Comment, Mixed, Code]
+    );
+
+    let names: Vec<String> = ["c", "cpp", "cs", "go", "java", "js", "ts", "kt", "swift"]
+        .iter()
+        .map(|extension| Syntax::preset(extension).unwrap().name)
+        .collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "C",
+            "C++",
+            "C#",
+            "Go",
+            "Java",
+            "JavaScript",
+            "TypeScript",
+            "Kotlin",
+            "Swift"
+        ]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn custom_syntax_test() {
+    let syntax = Syntax::new("Custom")
+        .line_comment("REM")
+        .string("[", "]")
+        .escape(None);
+    let sl = vec![
+        String::from("REM comment"),
+        String::from("PRINT [REM \\]"),
+        String::from("PRINT ] REM"),
+    ];
+
+    assert_eq!(
+        sl.classify(&syntax),
+        vec![LineKind::Comment, LineKind::Code, LineKind::Mixed]
+    );
+    assert_eq!(
+        create_string().classify(&Syntax::new("Plain")),
+        vec![LineKind::Code; 10]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn sloc_report_test() {
+    let s = String::from("// a\n\nfn main() {} // b\nmain();\n");
+    let report = s.sloc(&Syntax::preset("rust").unwrap());
+
+    assert_eq!(
+        report,
+        SlocReport {
+            blank: 1,
+            comment: 1,
+            code: 1,
+            mixed: 1
+        }
+    );
+    assert_eq!(report.total(), s.count());
+
+    let mut total = SlocReport::new();
+    total += report;
+    total.record(LineKind::Code);
+
+    assert_eq!(
+        total,
+        report
+            + SlocReport {
+                code: 1,
+                ..SlocReport::new()
+            }
+    );
+    assert_eq!(
+        render_report(&[(String::from("a.rs"), report)], TableStyle::Ascii),
+        vec![
+            "+-------+-------+---------+------+-------+",
+            "| File  | Blank | Comment | Code | Mixed |",
+            "+=======+=======+=========+======+=======+",
+            "| a.rs  |     1 |       1 |    1 |     1 |",
+            "| Total |     1 |       1 |    1 |     1 |",
+            "+-------+-------+---------+------+-------+",
+        ]
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
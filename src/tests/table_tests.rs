@@ -0,0 +1,245 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::align::*;
+use crate::diff::Overflow;
+use crate::lines::*;
+use crate::table::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+fn create_table() -> Table {
+    let row = |cells: &[&str]| cells.iter().map(|cell| String::from(*cell)).collect();
+
+    Table::new(vec![
+        row(&["a", "two\nlines"]),
+        row(&["long cell text", "x|y"]),
+        row(&["z"]),
+    ])
+    .header(row(&["name", "value"]))
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_ascii_test() {
+    assert_eq!(
+        create_table().render(&TableOptions::new(TableStyle::Ascii).row_lines(true)),
+        vec![
+            "+----------------+-------+",
+            "| name           | value |",
+            "+================+=======+",
+            "| a              | two   |",
+            "|                | lines |",
+            "+----------------+-------+",
+            "| long cell text | x|y   |",
+            "+----------------+-------+",
+            "| z              |       |",
+            "+----------------+-------+",
+        ]
+    );
+    assert_eq!(
+        Table::from(vec![vec![String::from("日本")]]).render(&TableOptions::default()),
+        vec!["+------+", "| 日本 |", "+------+"]
+    );
+    assert!(Table::default().render(&TableOptions::default()).is_empty());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_unicode_test() {
+    let options = TableOptions::new(TableStyle::Unicode)
+        .alignments(vec![Alignment::Right, Alignment::Center])
+        .max_widths(vec![Some(9)]);
+
+    assert_eq!(
+        create_table().render(&options),
+        vec![
+            "┌───────────┬───────┐",
+            "│      name │ value │",
+            "╞═══════════╪═══════╡",
+            "│         a │  two  │",
+            "│           │ lines │",
+            "├───────────┼───────┤",
+            "│ long cell │  x|y  │",
+            "│      text │       │",
+            "├───────────┼───────┤",
+            "│         z │       │",
+            "└───────────┴───────┘",
+        ]
+    );
+    assert_eq!(
+        create_table().render(&options.overflow(Overflow::Truncate))[6],
+        "│ long cell │  x|y  │"
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn render_markdown_test() {
+    let options = TableOptions::new(TableStyle::Markdown)
+        .alignments(vec![Alignment::Left, Alignment::Right])
+        .max_widths(vec![Some(4)])
+        .overflow(Overflow::Truncate);
+
+    assert_eq!(
+        create_table().render(&options),
+        vec![
+            "| name |        value |",
+            "| ---- | -----------: |",
+            "| a    | two<br>lines |",
+            "| long |         x\\|y |",
+            "| z    |              |",
+        ]
+    );
+    assert_eq!(
+        Table::new(vec![vec![String::from("a")]])
+            .render(&TableOptions::new(TableStyle::Markdown).alignments(vec![Alignment::Center])),
+        vec!["|     |", "| :-: |", "|  a  |"]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn parse_round_trip_test() {
+    let table = create_table();
+    let mut expected = table.clone();
+    expected.rows[2].push(String::new());
+
+    for style in [TableStyle::Ascii, TableStyle::Unicode, TableStyle::Markdown].iter() {
+        let options = TableOptions::new(*style).row_lines(true);
+        assert_eq!(Table::parse(&table.render(&options)), Ok(expected.clone()));
+    }
+
+    let table = Table::new(vec![vec![String::from("日本"), String::from("a")]; 2]);
+    assert_eq!(
+        Table::parse(&table.render(&TableOptions::default().row_lines(true))),
+        Ok(table.clone())
+    );
+    assert_eq!(
+        Table::parse(&table.render(&TableOptions::new(TableStyle::Markdown))),
+        Ok(table.clone())
+    );
+    assert_eq!(
+        Table::parse(&table.render(&TableOptions::default())),
+        Ok(Table::new(vec![vec![
+            String::from("日本\n日本"),
+            String::from("a\na")
+        ]]))
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn parse_grid_round_trip_test() {
+    let cells = |cells: &[&str]| cells.iter().map(|cell| String::from(*cell)).collect();
+    let tables = [
+        Table::new(vec![cells(&["a|b", "x\ny"])]),
+        Table::new(vec![cells(&["a", "x\ny"]), cells(&["b", "z"])]).header(cells(&["k", "v"])),
+        Table::new(Vec::new()).header(cells(&["a"])),
+        Table::new(Vec::new()).header(cells(&["a", "two\nlines"])),
+    ];
+    let header_only = String::from("+---+\n| a |\n+===+").split();
+
+    assert_eq!(Table::parse(&header_only), Ok(tables[2].clone()));
+
+    for table in tables.iter() {
+        for style in [TableStyle::Ascii, TableStyle::Unicode].iter() {
+            for row_lines in [false, true].iter() {
+                let options = TableOptions::new(*style).row_lines(*row_lines);
+                assert_eq!(Table::parse(&table.render(&options)), Ok(table.clone()));
+            }
+        }
+    }
+}
+
+/************************************************************************************************/
+
+#[test]
+fn tables_test() {
+    let s = String::from(
+        "Intro\n\
+         \x20 +---+---+\n\
+         \x20 | a | b |\n\
+         \x20 +---+---+\n\
+         \x20 | c |   |\n\
+         \x20 +---+---+\n\
+         a | b\n\
+         :-: | ---\n\
+         1 | 2 \\| 3\n\
+         4\n\
+         | no | separator |",
+    );
+    let tables = s.tables();
+
+    assert_eq!(tables.len(), 2);
+    assert_eq!(tables[0].range, 1..6);
+    assert_eq!(tables[0].style, TableStyle::Ascii);
+    assert_eq!(tables[0].table.header, None);
+    assert_eq!(tables[0].table.rows, vec![vec!["a", "b"], vec!["c", ""]]);
+    assert_eq!(tables[1].range, 6..9);
+    assert_eq!(
+        tables[1].options().alignments,
+        vec![Alignment::Center, Alignment::Left]
+    );
+    assert_eq!(tables[1].table.rows, vec![vec!["1", "2 | 3"]]);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn tables_in_prose_test() {
+    let s = String::from(
+        "Some prose with a ---------- rule
+         and a | pipe in it.
+         +-----+ not a table
+         
+         | name | size |
+         |------|-----:|
+         | a    |   10 |
+         
+         +---+
+         | x |
+         More prose.",
+    );
+    let tables = s.tables();
+
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].range, 4..7);
+    assert_eq!(tables[0].table.rows, vec![vec!["a", "10"]]);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn parse_errors_test() {
+    let error = |s: &str| {
+        Table::parse(&String::from(s).split())
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(error(""), "invalid table: there is no table");
+    assert_eq!(error("+--+\n| a |"), "line 1: unterminated table");
+    assert_eq!(error("+--+\nx\n+--+"), "line 2: expected a row or a border");
+    assert_eq!(error("| a |\n| b |"), "line 2: missing the separator row");
+    assert_eq!(
+        error("| a | b |\n| - |"),
+        "line 2: the separator row has 1 cells, the header row has 2"
+    );
+    assert_eq!(
+        error("| a |\n| - |\n\ntext"),
+        "line 3: text after the table"
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,112 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use crate::tabs::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn expand_tabs_test() {
+    let s = String::from("\tline0\n  \tline1\nab\tc");
+    let options = TabOptions::new(TabStops::Uniform(4));
+
+    let e = s.clone().expand_tabs(&options);
+    assert_eq!(e.get(0), "    line0");
+    assert_eq!(e.get(1), "    line1");
+    assert_eq!(e.get(2), "ab  c");
+
+    let e = s.expand_tabs(&options.leading_only(true));
+    assert_eq!(e.get(2), "ab\tc");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn expand_tabs_list_test() {
+    let sl = vec![String::from("a\tb\tc\td\te")];
+    let options = TabOptions::new("2,5,6".parse().unwrap());
+
+    assert_eq!(sl.expand_tabs(&options), vec!["a b  c d e"]);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn expand_tabs_wide_characters_test() {
+    let sl = vec![String::from("日本\tx"), String::from("e\u{301}\tx")];
+    let options = TabOptions::new(TabStops::Uniform(8));
+
+    assert_eq!(
+        sl.expand_tabs(&options),
+        vec!["日本    x", "e\u{301}       x"]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn unexpand_tabs_test() {
+    let s = String::from("        line0   x\n    line1\n   line2");
+    let options = TabOptions::new(TabStops::Uniform(4)).leading_only(true);
+
+    let u = s.clone().unexpand_tabs(&options);
+    assert_eq!(u.get(0), "\t\tline0   x");
+    assert_eq!(u.get(1), "\tline1");
+    assert_eq!(u.get(2), "   line2");
+
+    let u = s.unexpand_tabs(&options.leading_only(false));
+    assert_eq!(u.get(0), "\t\tline0\tx");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn unexpand_tabs_list_test() {
+    let options = TabOptions::new(TabStops::List(vec![4]));
+
+    assert_eq!(String::from("ab  \tc").unexpand_tabs(&options), "ab\t\tc");
+    assert_eq!(
+        String::from("    ab  \tc").unexpand_tabs(&options),
+        "\tab  \tc"
+    );
+    assert_eq!(
+        String::from("    ab  \tc")
+            .unexpand_tabs(&options)
+            .expand_tabs(&options),
+        "    ab   c"
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn tab_stops_parse_test() {
+    assert_eq!("8".parse::<TabStops>(), Ok(TabStops::Uniform(8)));
+    assert_eq!(
+        "4, 8,12".parse::<TabStops>(),
+        Ok(TabStops::List(vec![4, 8, 12]))
+    );
+    assert!("".parse::<TabStops>().is_err());
+    assert!("0".parse::<TabStops>().is_err());
+    assert!("8,4".parse::<TabStops>().is_err());
+    assert!("a".parse::<TabStops>().is_err());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn display_column_test() {
+    let stops = TabStops::Uniform(4);
+
+    assert_eq!(display_column("ab\tc", 3, &stops), 4);
+    assert_eq!(display_column("ab\tc", 2, &stops), 2);
+    assert_eq!(display_column("\t\tc", 2, &stops), 8);
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,81 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::whitespace::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn trim_trailing_whitespace_test() {
+    let r = String::from("line0 \t\nline1\n   \nline3\n").trim_trailing_whitespace();
+
+    assert_eq!(r.text, "line0\nline1\n\nline3\n");
+    assert_eq!(r.changed, vec![1, 3]);
+
+    let r = String::from("line0\nline1").trim_trailing_whitespace();
+
+    assert_eq!(r.text, "line0\nline1");
+    assert!(r.changed.is_empty());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn collapse_blank_lines_test() {
+    let s = String::from("\n\n\nline3\n\n \nline6\n\n");
+
+    let r = s.clone().collapse_blank_lines(0);
+    assert_eq!(r.text, "line3\nline6\n");
+    assert_eq!(r.changed, vec![1, 2, 3, 5, 6, 8]);
+
+    let r = s.collapse_blank_lines(2);
+    assert_eq!(r.text, "\n\nline3\n\n \nline6\n\n");
+    assert_eq!(r.changed, vec![3]);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn trim_blank_lines_test() {
+    let r = String::from("line0\n\nline2").trim_blank_lines();
+    assert_eq!(r.text, "line0\n\nline2");
+    assert!(r.changed.is_empty());
+
+    let r = String::from(" \n\t\n").trim_blank_lines();
+    assert_eq!(r.text, "");
+    assert_eq!(r.changed, vec![1, 2]);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn ensure_final_newline_test() {
+    let r = String::from("line0\n").ensure_final_newline();
+    assert_eq!(r.text, "line0\n");
+    assert!(r.changed.is_empty());
+
+    let r = String::from("\n\n").ensure_final_newline();
+    assert_eq!(r.text, "");
+    assert_eq!(r.changed, vec![1, 2]);
+
+    let r = String::new().ensure_final_newline();
+    assert_eq!(r.text, "");
+    assert!(r.changed.is_empty());
+}
+
+/************************************************************************************************/
+
+#[test]
+fn normalize_whitespace_test() {
+    let r = String::from("  \nline1\t\n\n\n\nline5  \n\n").normalize_whitespace(1);
+
+    assert_eq!(r.text, "line1\n\nline5\n");
+    assert_eq!(r.changed, vec![1, 2, 4, 5, 6, 7]);
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,75 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::align::*;
+use crate::tests::helper::*;
+use crate::width::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn display_width_test() {
+    assert_eq!(display_width(""), 0);
+    assert_eq!(display_width("line0"), 5);
+    assert_eq!(display_width("ｆｕｌｌ"), 8);
+    assert_eq!(display_width("a\u{300}\u{316}b"), 2);
+    assert_eq!(display_width("🇳🇱🇯🇵"), 4);
+    assert_eq!(
+        text_width("±", &WidthOptions::new().ambiguous_wide(true)),
+        2
+    );
+    assert_eq!(create_string().max_width(&WidthOptions::default()), 5);
+}
+
+/************************************************************************************************/
+
+#[test]
+fn pad_width_test() {
+    assert_eq!(pad_text("é", 3, Alignment::Right), "  é");
+    assert_eq!(center_text("日", 5), " 日  ");
+    assert_eq!(center_text("toolong", 3), "toolong");
+    assert_eq!(
+        create_string_lines().pad_width(7, Alignment::Center)[0],
+        " line0 "
+    );
+    assert_eq!(
+        String::from("a\n日本").pad_width(4, Alignment::Left),
+        "a   \n日本"
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn truncate_width_test() {
+    let options = WidthOptions::new();
+    let middle = options.clone().position(EllipsisPosition::Middle);
+    let start = options.clone().position(EllipsisPosition::Start);
+
+    assert_eq!(truncate_text("abc", 3, &options), "abc");
+    assert_eq!(truncate_text("abcd", 3, &options), "ab…");
+    assert_eq!(truncate_text("日本語", 4, &options), "日…");
+    assert_eq!(truncate_text("日本語", 4, &start), "…語");
+    assert_eq!(truncate_text("abcdefg", 6, &middle), "abc…fg");
+    assert_eq!(
+        truncate_text("e\u{301}e\u{301}e\u{301}", 2, &options),
+        "e\u{301}…"
+    );
+    assert_eq!(truncate_text("👩‍🔬👩‍🔬", 3, &options), "👩‍🔬…");
+    assert_eq!(
+        truncate_text("abcdef", 2, &options.clone().ellipsis("...")),
+        "ab"
+    );
+    assert_eq!(truncate_text("abc", 0, &options), "");
+    assert_eq!(
+        create_string().truncate_width(4, &options.ellipsis("~")),
+        "lin~\nlin~\nlin~\nlin~\nlin~\nlin~\nlin~\nlin~\nlin~\nlin~"
+    );
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,160 @@
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use crate::wrap::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+#[test]
+fn wrap_test() {
+    let s = String::from("one two three four five\n\nsix seven").wrap(&WrapOptions::new(9));
+
+    assert_eq!(s.get(0), "one two");
+    assert_eq!(s.get(1), "three");
+    assert_eq!(s.get(2), "four five");
+    assert_eq!(s.get(3), "");
+    assert_eq!(s.get(4), "six seven");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn reflow_test() {
+    let s = String::from("one two\nthree\nfour five\n\n\nsix\nseven").reflow(&WrapOptions::new(13));
+
+    assert_eq!(s, "one two three\nfour five\n\n\nsix seven");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn break_words_test() {
+    let sl = vec![String::from("abcdefghij klm")];
+
+    assert_eq!(
+        sl.clone().wrap(&WrapOptions::new(4)),
+        vec!["abcd", "efgh", "ij", "klm"]
+    );
+    assert_eq!(
+        sl.wrap(&WrapOptions::new(4).break_words(false)),
+        vec!["abcdefghij", "klm"]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn wide_characters_test() {
+    let sl = vec![String::from("日本語の テキスト")];
+
+    assert_eq!(
+        sl.clone().wrap(&WrapOptions::new(8)),
+        vec!["日本語の", "テキスト"]
+    );
+    assert_eq!(
+        sl.wrap(&WrapOptions::new(5)),
+        vec!["日本", "語の", "テキ", "スト"]
+    );
+}
+
+/************************************************************************************************/
+
+#[test]
+fn indent_test() {
+    let options = WrapOptions::new(10)
+        .initial_indent("* ")
+        .subsequent_indent("  ");
+    let s = String::from("aaa bbb ccc ddd").wrap(&options);
+
+    assert_eq!(s, "* aaa bbb\n  ccc ddd");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn justify_test() {
+    let text = String::from("aa bb cc dd ee");
+
+    let s = text
+        .clone()
+        .wrap(&WrapOptions::new(9).justify(Justify::Right));
+    assert_eq!(s, " aa bb cc\n    dd ee");
+
+    let s = text
+        .clone()
+        .wrap(&WrapOptions::new(9).justify(Justify::Center));
+    assert_eq!(s, "aa bb cc\n  dd ee");
+
+    let s = text.wrap(&WrapOptions::new(12).justify(Justify::Full));
+    assert_eq!(s, "aa  bb cc dd\nee");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn reflow_prefixed_test() {
+    let s = String::from("    // one two three\n    // four\n    //\n    // five")
+        .reflow_prefixed(&WrapOptions::new(17));
+
+    assert_eq!(s, "    // one two\n    // three four\n    //\n    // five");
+
+    let s = String::from("/**\n * one two\n * three\n */").reflow_prefixed(&WrapOptions::new(20));
+
+    assert_eq!(s, "/**\n * one two three\n */");
+
+    let s = String::from("#no space\n#here").reflow_prefixed(&WrapOptions::new(20));
+
+    assert_eq!(s, "#no space #here");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn reflow_prefixed_markers_test() {
+    let s = String::from("#!/bin/sh\n# one two\n#  three").reflow_prefixed(&WrapOptions::new(20));
+
+    assert_eq!(s, "#!/bin/sh\n# one two three");
+
+    let s = String::from("/path/to/file\n/usr/bin").reflow_prefixed(&WrapOptions::new(40));
+
+    assert_eq!(s, "/path/to/file /usr/bin");
+
+    assert_eq!(line_prefix("#!/bin/sh"), "");
+    assert_eq!(line_prefix("/path"), "");
+    assert_eq!(line_prefix("#no"), "");
+    assert_eq!(line_prefix("## heading"), "## ");
+    assert_eq!(line_prefix("  //! doc"), "  //! ");
+    assert_eq!(line_prefix(">> quote"), ">> ");
+    assert_eq!(line_prefix(">"), ">");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn reflow_prefixed_nested_test() {
+    let s = String::from("> a b\n> > c d e\n> > f\n> g h").reflow_prefixed(&WrapOptions::new(7));
+
+    assert_eq!(s.get(0), "> a b");
+    assert_eq!(s.get(1), "> > c d");
+    assert_eq!(s.get(2), "> > e f");
+    assert_eq!(s.get(3), "> g h");
+}
+
+/************************************************************************************************/
+
+#[test]
+fn common_prefix_test() {
+    let sl = String::from("  # a\n  ## b\n\n  # c").split();
+    assert_eq!(common_prefix(&sl), "  #");
+
+    let sl = String::from("text\n> quote").split();
+    assert_eq!(common_prefix(&sl), "");
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,305 @@
+//! `Whitespace` is a module that contains traits to clean up whitespace in lines of a String.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The result of a whitespace operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The resulting text.
+    pub text: String,
+    /// The (one based) numbers of the original lines that were changed or removed, in ascending
+    /// order.
+    pub changed: Vec<usize>,
+}
+
+/************************************************************************************************/
+
+/// The `Whitespace` trait contains all functions to be implemented that clean up whitespace.
+/// Every function returns a `Report` with the resulting text and the numbers of the lines that
+/// were changed.
+pub trait Whitespace {
+    /// Removes trailing whitespace from every line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::whitespace::*;
+    ///
+    /// let r = String::from("line0  \nline1\nline2\t").trim_trailing_whitespace();
+    ///
+    /// assert_eq!(r.text, "line0\nline1\nline2");
+    /// assert_eq!(r.changed, vec![1, 3]);
+    /// ```
+    fn trim_trailing_whitespace(self) -> Report;
+
+    /// Collapses runs of blank lines to a maximum amount of lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - the maximum amount of consecutive blank lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::whitespace::*;
+    ///
+    /// let r = String::from("line0\n\n\n\nline4").collapse_blank_lines(1);
+    ///
+    /// assert_eq!(r.text, "line0\n\nline4");
+    /// assert_eq!(r.changed, vec![3, 4]);
+    /// ```
+    fn collapse_blank_lines(self, max: usize) -> Report;
+
+    /// Removes the blank lines at the beginning and at the end of the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::whitespace::*;
+    ///
+    /// let r = String::from("\n  \nline2\nline3\n\n").trim_blank_lines();
+    ///
+    /// assert_eq!(r.text, "line2\nline3\n");
+    /// assert_eq!(r.changed, vec![1, 2, 5]);
+    /// ```
+    fn trim_blank_lines(self) -> Report;
+
+    /// Makes sure a non-empty text ends with exactly one newline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::whitespace::*;
+    ///
+    /// let r = String::from("line0\nline1").ensure_final_newline();
+    ///
+    /// assert_eq!(r.text, "line0\nline1\n");
+    /// assert_eq!(r.changed, vec![2]);
+    ///
+    /// let r = String::from("line0\nline1\n\n\n").ensure_final_newline();
+    ///
+    /// assert_eq!(r.text, "line0\nline1\n");
+    /// assert_eq!(r.changed, vec![3, 4]);
+    /// ```
+    fn ensure_final_newline(self) -> Report;
+
+    /// Applies all whitespace operations: trims trailing whitespace, trims and collapses blank
+    /// lines and ensures a single final newline.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_blank_lines` - the maximum amount of consecutive blank lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::whitespace::*;
+    ///
+    /// let r = String::from("\nline1 \n\n \n\nline5").normalize_whitespace(1);
+    ///
+    /// assert_eq!(r.text, "line1\n\nline5\n");
+    /// assert_eq!(r.changed, vec![1, 2, 4, 5, 6]);
+    /// ```
+    fn normalize_whitespace(self, max_blank_lines: usize) -> Report;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl Whitespace for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn trim_trailing_whitespace(self) -> Report {
+        let mut text = Text::new(&self);
+        text.trim_trailing_whitespace();
+        text.report()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn collapse_blank_lines(self, max: usize) -> Report {
+        let mut text = Text::new(&self);
+        text.collapse_blank_lines(max);
+        text.report()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn trim_blank_lines(self) -> Report {
+        let mut text = Text::new(&self);
+        text.trim_blank_lines();
+        text.report()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn ensure_final_newline(self) -> Report {
+        let mut text = Text::new(&self);
+        text.ensure_final_newline();
+        text.report()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn normalize_whitespace(self, max_blank_lines: usize) -> Report {
+        let mut text = Text::new(&self);
+        text.trim_trailing_whitespace();
+        text.trim_blank_lines();
+        text.collapse_blank_lines(max_blank_lines);
+        text.ensure_final_newline();
+        text.report()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The lines of a text together with their original (one based) numbers, so changes can be
+/// reported even after lines were removed.
+struct Text {
+    lines: Vec<(usize, String)>,
+    changed: Vec<usize>,
+    final_newline: bool,
+}
+
+/************************************************************************************************/
+
+impl Text {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn new(s: &str) -> Self {
+        Text {
+            lines: s
+                .lines()
+                .enumerate()
+                .map(|(index, line)| (index + 1, String::from(line)))
+                .collect(),
+            changed: Vec::new(),
+            final_newline: s.ends_with('\n'),
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn report(mut self) -> Report {
+        let mut text = self
+            .lines
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect::<StringLines>()
+            .merge();
+
+        if self.final_newline {
+            text.push('\n');
+        }
+
+        self.changed.sort_unstable();
+        self.changed.dedup();
+
+        Report {
+            text,
+            changed: self.changed,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn trim_trailing_whitespace(&mut self) {
+        for (number, line) in self.lines.iter_mut() {
+            let trimmed = line.trim_end().len();
+            if trimmed < line.len() {
+                line.truncate(trimmed);
+                self.changed.push(*number);
+            }
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn collapse_blank_lines(&mut self, max: usize) {
+        let mut run = 0;
+        let changed = &mut self.changed;
+
+        self.lines.retain(|(number, line)| {
+            if !is_blank(line) {
+                run = 0;
+                return true;
+            }
+
+            run += 1;
+            if run > max {
+                changed.push(*number);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn trim_blank_lines(&mut self) {
+        while self.lines.first().is_some_and(|(_, line)| is_blank(line)) {
+            let (number, _) = self.lines.remove(0);
+            self.changed.push(number);
+        }
+
+        while self.lines.last().is_some_and(|(_, line)| is_blank(line)) {
+            let (number, _) = self.lines.remove(self.lines.len() - 1);
+            self.changed.push(number);
+        }
+
+        if self.lines.is_empty() {
+            self.final_newline = false;
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn ensure_final_newline(&mut self) {
+        while self.lines.len() > 1 && self.lines.last().is_some_and(|(_, line)| line.is_empty()) {
+            let (number, _) = self.lines.remove(self.lines.len() - 1);
+            self.changed.push(number);
+        }
+
+        match self.lines.last() {
+            Some((_, line)) if line.is_empty() => {
+                // a text that consists of newlines only will be empty
+                let (number, _) = self.lines.remove(0);
+                self.changed.push(number);
+                self.final_newline = false;
+            }
+            Some((number, _)) => {
+                if !self.final_newline {
+                    self.changed.push(*number);
+                    self.final_newline = true;
+                }
+            }
+            None => self.final_newline = false,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,445 @@
+//! `Width` is a module that contains traits and functions to measure, pad and truncate text by
+//! its display width, the amount of terminal columns it takes. Text is measured per grapheme
+//! cluster, so combining marks and emoji sequences are never split.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::align::Alignment;
+use crate::lines::*;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The place where a truncated text is shortened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EllipsisPosition {
+    /// The start of the text is replaced by the ellipsis.
+    Start,
+    /// The middle of the text is replaced by the ellipsis.
+    Middle,
+    /// The end of the text is replaced by the ellipsis, this is the default.
+    End,
+}
+
+/************************************************************************************************/
+
+/// The options used when measuring and truncating text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WidthOptions {
+    /// Counts East Asian ambiguous characters, like `±` or `Ω`, as wide when `true`, as is
+    /// usual in CJK contexts.
+    pub ambiguous_wide: bool,
+    /// The text that marks a truncation, may be empty.
+    pub ellipsis: String,
+    /// The place where a truncated text is shortened.
+    pub position: EllipsisPosition,
+}
+
+/************************************************************************************************/
+
+/// The `WidthLines` trait contains all functions to be implemented that measure, pad and
+/// truncate lines by their display width.
+pub trait WidthLines {
+    /// Returns the display width of the widest line.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for measuring.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::width::*;
+    ///
+    /// let s = String::from("abc\n日本語\ne\u{301}");
+    ///
+    /// assert_eq!(s.max_width(&WidthOptions::new()), 6);
+    /// ```
+    fn max_width(&self, options: &WidthOptions) -> usize;
+
+    /// Pads every line with spaces to a display width, lines that are wider are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the display width.
+    /// * `alignment` - the alignment of the text within the width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::align::*;
+    /// use rich_lib::width::*;
+    ///
+    /// let s = String::from("ab\n日本");
+    ///
+    /// assert_eq!(s.pad_width(5, Alignment::Right), "   ab\n 日本");
+    /// ```
+    fn pad_width(self, width: usize, alignment: Alignment) -> Self;
+
+    /// Centres every line within a display width, an odd amount of padding puts the extra
+    /// space on the right.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the display width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::width::*;
+    ///
+    /// assert_eq!(String::from("ab\nc").center_width(4), " ab \n c  ");
+    /// ```
+    fn center_width(self, width: usize) -> Self;
+
+    /// Truncates every line that is wider than a display width, the ellipsis is included in
+    /// the width.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the display width.
+    /// * `options` - the options used for truncating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::width::*;
+    ///
+    /// let s = String::from("abcdefgh\nshort");
+    /// let options = WidthOptions::new().position(EllipsisPosition::Middle);
+    ///
+    /// assert_eq!(s.truncate_width(5, &options), "ab…gh\nshort");
+    /// ```
+    fn truncate_width(self, width: usize, options: &WidthOptions) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl WidthOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options, which count ambiguous characters as narrow and truncate at
+    /// the end with `…`.
+    pub fn new() -> Self {
+        WidthOptions {
+            ambiguous_wide: false,
+            ellipsis: String::from("…"),
+            position: EllipsisPosition::End,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether ambiguous characters are counted as wide.
+    pub fn ambiguous_wide(mut self, ambiguous_wide: bool) -> Self {
+        self.ambiguous_wide = ambiguous_wide;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the text that marks a truncation.
+    pub fn ellipsis(mut self, ellipsis: &str) -> Self {
+        self.ellipsis = String::from(ellipsis);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the place where a truncated text is shortened.
+    pub fn position(mut self, position: EllipsisPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl Default for WidthOptions {
+    fn default() -> Self {
+        WidthOptions::new()
+    }
+}
+
+/************************************************************************************************/
+
+impl WidthLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn max_width(&self, options: &WidthOptions) -> usize {
+        self.iter()
+            .map(|line| text_width(line, options))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn pad_width(self, width: usize, alignment: Alignment) -> Self {
+        self.iter()
+            .map(|line| pad_text(line, width, alignment))
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn center_width(self, width: usize) -> Self {
+        self.pad_width(width, Alignment::Center)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn truncate_width(self, width: usize, options: &WidthOptions) -> Self {
+        self.iter()
+            .map(|line| truncate_text(line, width, options))
+            .collect()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl WidthLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn max_width(&self, options: &WidthOptions) -> usize {
+        self.lines()
+            .map(|line| text_width(line, options))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn pad_width(self, width: usize, alignment: Alignment) -> Self {
+        self.split().pad_width(width, alignment).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn center_width(self, width: usize) -> Self {
+        self.split().center_width(width).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn truncate_width(self, width: usize, options: &WidthOptions) -> Self {
+        self.split().truncate_width(width, options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns the display width of a text, counting ambiguous characters as narrow.
+///
+/// # Arguments
+///
+/// * `text` - the text to measure.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::width::*;
+///
+/// assert_eq!("abc".len(), display_width("abc"));
+/// assert_eq!("日本".len(), 6);
+/// assert_eq!(display_width("日本"), 4);
+/// assert_eq!(display_width("e\u{301}"), 1);
+/// assert_eq!(display_width("👩‍🔬"), 2);
+/// ```
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true)
+        .map(|grapheme| grapheme_width(grapheme, false))
+        .sum()
+}
+
+/************************************************************************************************/
+
+/// Returns the display width of a text.
+///
+/// # Arguments
+///
+/// * `text` - the text to measure.
+/// * `options` - the options used for measuring.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::width::*;
+///
+/// assert_eq!(text_width("±1", &WidthOptions::new()), 2);
+/// assert_eq!(text_width("±1", &WidthOptions::new().ambiguous_wide(true)), 3);
+/// ```
+pub fn text_width(text: &str, options: &WidthOptions) -> usize {
+    text.graphemes(true)
+        .map(|grapheme| grapheme_width(grapheme, options.ambiguous_wide))
+        .sum()
+}
+
+/************************************************************************************************/
+
+/// Pads a text with spaces to a display width, a wider text is kept.
+///
+/// # Arguments
+///
+/// * `text` - the text to pad.
+/// * `width` - the display width.
+/// * `alignment` - the alignment of the text within the width.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::align::*;
+/// use rich_lib::width::*;
+///
+/// assert_eq!(pad_text("日本", 6, Alignment::Left), "日本  ");
+/// assert_eq!(pad_text("abc", 2, Alignment::Right), "abc");
+/// ```
+pub fn pad_text(text: &str, width: usize, alignment: Alignment) -> String {
+    pad(text, width, alignment, true)
+}
+
+/************************************************************************************************/
+
+/// Centres a text within a display width, an odd amount of padding puts the extra space on the
+/// right.
+///
+/// # Arguments
+///
+/// * `text` - the text to centre.
+/// * `width` - the display width.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::width::*;
+///
+/// assert_eq!(center_text("日本", 7), " 日本  ");
+/// ```
+pub fn center_text(text: &str, width: usize) -> String {
+    pad(text, width, Alignment::Center, true)
+}
+
+/************************************************************************************************/
+
+/// Truncates a text that is wider than a display width on grapheme boundaries, the ellipsis is
+/// included in the width. The ellipsis is left out when it does not fit.
+///
+/// # Arguments
+///
+/// * `text` - the text to truncate.
+/// * `width` - the display width.
+/// * `options` - the options used for truncating.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::width::*;
+///
+/// let options = WidthOptions::new();
+/// let start = options.clone().position(EllipsisPosition::Start);
+///
+/// assert_eq!(truncate_text("日本語です", 7, &options), "日本語…");
+/// assert_eq!(truncate_text("abcdef", 4, &start), "…def");
+/// assert_eq!(truncate_text("abcdef", 4, &options.ellipsis("")), "abcd");
+/// ```
+pub fn truncate_text(text: &str, width: usize, options: &WidthOptions) -> String {
+    if text_width(text, options) <= width {
+        return String::from(text);
+    }
+
+    let ellipsis_width = text_width(&options.ellipsis, options);
+    let (ellipsis, available) = if ellipsis_width <= width {
+        (options.ellipsis.as_str(), width - ellipsis_width)
+    } else {
+        ("", width)
+    };
+    let graphemes: Vec<(&str, usize)> = text
+        .graphemes(true)
+        .map(|grapheme| (grapheme, grapheme_width(grapheme, options.ambiguous_wide)))
+        .collect();
+
+    let head = match options.position {
+        EllipsisPosition::End => available,
+        EllipsisPosition::Start => 0,
+        EllipsisPosition::Middle => available - available / 2,
+    };
+    let head = take_width(graphemes.iter(), head);
+    let used = head.iter().map(|(_, width)| width).sum::<usize>();
+    let mut tail = take_width(graphemes.iter().rev(), available - used);
+    tail.reverse();
+
+    head.iter()
+        .map(|(grapheme, _)| *grapheme)
+        .chain(Some(ellipsis))
+        .chain(tail.iter().map(|(grapheme, _)| *grapheme))
+        .collect()
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Returns the display width of a single grapheme cluster. Emoji sequences, which consist of
+/// several wide characters, take two columns.
+pub(crate) fn grapheme_width(grapheme: &str, ambiguous_wide: bool) -> usize {
+    let width = if ambiguous_wide {
+        grapheme.width_cjk()
+    } else {
+        grapheme.width()
+    };
+
+    width.min(2)
+}
+
+/************************************************************************************************/
+
+/// Pads a text with spaces to a display width. The padding on the right is left out when
+/// `trailing` is `false`.
+pub(crate) fn pad(text: &str, width: usize, alignment: Alignment, trailing: bool) -> String {
+    let free = width.saturating_sub(display_width(text));
+    let (left, right) = match alignment {
+        Alignment::Left => (0, free),
+        Alignment::Right => (free, 0),
+        Alignment::Center => (free / 2, free - free / 2),
+    };
+    let right = if trailing { right } else { 0 };
+
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+/************************************************************************************************/
+
+/// Returns the leading graphemes that fit in a display width.
+fn take_width<'a, I>(graphemes: I, width: usize) -> Vec<(&'a str, usize)>
+where
+    I: Iterator<Item = &'a (&'a str, usize)>,
+{
+    let mut used = 0;
+
+    graphemes
+        .take_while(|(_, grapheme_width)| {
+            used += grapheme_width;
+            used <= width
+        })
+        .copied()
+        .collect()
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
@@ -0,0 +1,536 @@
+//! `Wrap` is a module that contains traits to wrap and reflow lines to a target width.
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+use crate::lines::*;
+use crate::width::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// The way wrapped lines are justified within the target width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Justify {
+    /// Lines are aligned to the left, this is the default.
+    Left,
+    /// Lines are aligned to the right.
+    Right,
+    /// Lines are centred.
+    Center,
+    /// Spaces between words are stretched so all lines, except the last line of a paragraph,
+    /// fill the complete width.
+    Full,
+}
+
+/************************************************************************************************/
+
+/// The options used when wrapping or reflowing lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrapOptions {
+    /// The maximum display width of a line, including the indent.
+    pub width: usize,
+    /// The indent of the first line of a paragraph.
+    pub initial_indent: String,
+    /// The indent of all other lines of a paragraph.
+    pub subsequent_indent: String,
+    /// Breaks words that do not fit on a line when `true`, otherwise these words will overflow.
+    pub break_words: bool,
+    /// The justification of the lines.
+    pub justify: Justify,
+}
+
+/************************************************************************************************/
+
+/// The `WrapLines` trait contains all functions to be implemented that wrap lines to a target
+/// width.
+pub trait WrapLines {
+    /// Wraps every line separately to the width given in the options. Blank lines are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for wrapping.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::wrap::*;
+    ///
+    /// let s = String::from("the quick brown fox\njumps").wrap(&WrapOptions::new(10));
+    ///
+    /// assert_eq!(s, "the quick\nbrown fox\njumps");
+    /// ```
+    fn wrap(self, options: &WrapOptions) -> Self;
+
+    /// Joins consecutive non-blank lines into paragraphs and wraps these to the width given in
+    /// the options. Blank lines separating the paragraphs are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for reflowing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::wrap::*;
+    ///
+    /// let s = String::from("the quick\nbrown fox jumps\n\nover")
+    ///             .reflow(&WrapOptions::new(16).justify(Justify::Full));
+    ///
+    /// assert_eq!(s, "the  quick brown\nfox jumps\n\nover");
+    /// ```
+    fn reflow(self, options: &WrapOptions) -> Self;
+
+    /// Reflows lines that start with a prefix, like `// ` or `# ` comments, `* ` doc blocks or
+    /// `> ` quotes. Consecutive lines with the same prefix are joined into paragraphs, wrapped to
+    /// the width given in the options (minus the width of the prefix) and the prefix is re-applied
+    /// to every wrapped line. Lines with a different prefix, like nested quotes, form a separate
+    /// paragraph. Lines that contain only a prefix are kept as paragraph breaks.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the options used for reflowing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rich_lib::wrap::*;
+    ///
+    /// let s = String::from("> one two\n> three\n> > four five six\n>\n> seven")
+    ///             .reflow_prefixed(&WrapOptions::new(11));
+    ///
+    /// assert_eq!(s, "> one two\n> three\n> > four\n> > five\n> > six\n>\n> seven");
+    /// ```
+    fn reflow_prefixed(self, options: &WrapOptions) -> Self;
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+impl WrapOptions {
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Creates the default options for a given width.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the maximum display width of a line.
+    pub fn new(width: usize) -> Self {
+        WrapOptions {
+            width,
+            initial_indent: String::new(),
+            subsequent_indent: String::new(),
+            break_words: true,
+            justify: Justify::Left,
+        }
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the indent of the first line of a paragraph.
+    pub fn initial_indent(mut self, indent: &str) -> Self {
+        self.initial_indent = String::from(indent);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the indent of all other lines of a paragraph.
+    pub fn subsequent_indent(mut self, indent: &str) -> Self {
+        self.subsequent_indent = String::from(indent);
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets whether words that do not fit on a line will be broken.
+    pub fn break_words(mut self, break_words: bool) -> Self {
+        self.break_words = break_words;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    /// Sets the justification of the lines.
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl WrapLines for StringLines {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn wrap(self, options: &WrapOptions) -> Self {
+        let mut result = StringLines::new();
+
+        for line in self.iter() {
+            if line.trim().is_empty() {
+                result.push(String::new());
+            } else {
+                result.append(&mut wrap_paragraph(line, options));
+            }
+        }
+
+        result
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn reflow(self, options: &WrapOptions) -> Self {
+        let mut result = StringLines::new();
+        let mut paragraph = String::new();
+
+        for line in self.iter() {
+            if line.trim().is_empty() {
+                if !paragraph.is_empty() {
+                    result.append(&mut wrap_paragraph(&paragraph, options));
+                    paragraph.clear();
+                }
+                result.push(String::new());
+            } else {
+                paragraph.push(' ');
+                paragraph.push_str(line);
+            }
+        }
+
+        if !paragraph.is_empty() {
+            result.append(&mut wrap_paragraph(&paragraph, options));
+        }
+
+        result
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn reflow_prefixed(self, options: &WrapOptions) -> Self {
+        let mut result = StringLines::new();
+        let mut start = 0;
+
+        while let Some(line) = self.get(start) {
+            let prefix = line_prefix(line);
+
+            if is_prefix_only(line) {
+                result.push(String::from(line.trim_end()));
+                start += 1;
+                continue;
+            }
+
+            let end = start
+                + self[start..]
+                    .iter()
+                    .take_while(|next| {
+                        !is_prefix_only(next) && same_prefix(prefix, line_prefix(next))
+                    })
+                    .count();
+            let block = &self[start..end];
+            let prefix = common_prefix(block);
+            let paragraph: Vec<&str> = block
+                .iter()
+                .map(|line| line[prefix.len()..].trim())
+                .collect();
+
+            result.append(&mut wrap_prefixed(&prefix, &paragraph.join(" "), options));
+            start = end;
+        }
+
+        result
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+
+impl WrapLines for String {
+    /*------------------------------------------------------------------------------------------*/
+
+    fn wrap(self, options: &WrapOptions) -> Self {
+        self.split().wrap(options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn reflow(self, options: &WrapOptions) -> Self {
+        self.split().reflow(options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+
+    fn reflow_prefixed(self, options: &WrapOptions) -> Self {
+        self.split().reflow_prefixed(options).merge()
+    }
+
+    /*------------------------------------------------------------------------------------------*/
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/
+
+/// Wraps a single paragraph of text. Words are separated by whitespace, runs of whitespace are
+/// collapsed into a single space.
+///
+/// # Arguments
+///
+/// * `text` - the text of the paragraph.
+/// * `options` - the options used for wrapping.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::wrap::*;
+///
+/// let options = WrapOptions::new(12).initial_indent("- ").subsequent_indent("  ");
+/// let sl = wrap_paragraph("a list item  that wraps", &options);
+///
+/// assert_eq!(sl, vec!["- a list", "  item that", "  wraps"]);
+/// ```
+pub fn wrap_paragraph(text: &str, options: &WrapOptions) -> StringLines {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut row_width = 0;
+
+    for word in text.split_whitespace() {
+        let available = available_width(options, rows.len());
+        let word_width = display_width(word);
+        let needed = if row.is_empty() {
+            word_width
+        } else {
+            row_width + 1 + word_width
+        };
+
+        if needed <= available {
+            row.push(String::from(word));
+            row_width = needed;
+            continue;
+        }
+
+        if !row.is_empty() {
+            rows.push(std::mem::take(&mut row));
+        }
+
+        if word_width <= available_width(options, rows.len()) || !options.break_words {
+            row.push(String::from(word));
+            row_width = word_width;
+            continue;
+        }
+
+        let mut chunk = String::new();
+        let mut chunk_width = 0;
+
+        for grapheme in word.graphemes(true) {
+            let grapheme_width = display_width(grapheme);
+            if chunk_width + grapheme_width > available_width(options, rows.len())
+                && !chunk.is_empty()
+            {
+                rows.push(vec![chunk]);
+                chunk = String::new();
+                chunk_width = 0;
+            }
+            chunk.push_str(grapheme);
+            chunk_width += grapheme_width;
+        }
+
+        row.push(chunk);
+        row_width = chunk_width;
+    }
+
+    if !row.is_empty() {
+        rows.push(row);
+    }
+
+    let count = rows.len();
+    rows.iter()
+        .enumerate()
+        .map(|(index, words)| justify_row(words, options, index, index + 1 == count))
+        .collect()
+}
+
+/************************************************************************************************/
+
+/// Returns the prefix of a line. The prefix consists of the leading whitespace and comment or
+/// quote markers, like `//`, `#`, `*`, `>`, `;`, `--` and `%`. A marker is only part of the
+/// prefix when it is followed by whitespace or ends the line, so `#!/bin/sh` and `/path` have
+/// no prefix.
+///
+/// # Arguments
+///
+/// * `line` - the line to return the prefix of.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::wrap::*;
+///
+/// assert_eq!(line_prefix("  // some comment"), "  // ");
+/// assert_eq!(line_prefix("> > quoted"), "> > ");
+/// assert_eq!(line_prefix("#!/bin/sh"), "");
+/// assert_eq!(line_prefix("text"), "");
+/// ```
+pub fn line_prefix(line: &str) -> &str {
+    let mut end = line.len() - line.trim_start().len();
+
+    while let Some(marker) = PREFIX_MARKERS
+        .iter()
+        .find(|marker| line[end..].starts_with(*marker))
+    {
+        let rest = &line[end..];
+        let mut length = marker.len();
+        while rest[length..].starts_with(marker) {
+            length += marker.len();
+        }
+
+        let after = &rest[length..];
+        if !after.is_empty() && !after.starts_with(char::is_whitespace) {
+            break;
+        }
+        end += length + after.len() - after.trim_start().len();
+    }
+
+    &line[..end]
+}
+
+/************************************************************************************************/
+
+/// Returns the longest prefix that all non-blank lines have in common.
+///
+/// # Arguments
+///
+/// * `lines` - the lines to return the common prefix of.
+///
+/// # Example
+///
+/// ```
+/// use rich_lib::wrap::*;
+///
+/// let sl = vec![String::from("> > a"), String::from("> b"), String::from("")];
+///
+/// assert_eq!(common_prefix(&sl), "> ");
+/// ```
+pub fn common_prefix(lines: &[String]) -> String {
+    let mut common: Option<&str> = None;
+
+    for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+        let prefix = line_prefix(line);
+        common = Some(match common {
+            None => prefix,
+            Some(common) => {
+                let end = common
+                    .char_indices()
+                    .zip(prefix.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(common.len().min(prefix.len()), |((index, _), _)| index);
+                &common[..end]
+            }
+        });
+    }
+
+    String::from(common.unwrap_or(""))
+}
+
+/************************************************************************************************/
+
+/// The comment and quote markers, the longest markers first.
+const PREFIX_MARKERS: &[&str] = &[
+    "///", "//!", "/**", "//", "/*", "*/", "--", "#", "*", ">", ";", "!", "%",
+];
+
+/************************************************************************************************/
+
+fn same_prefix(a: &str, b: &str) -> bool {
+    a.trim_end() == b.trim_end()
+}
+
+/************************************************************************************************/
+
+fn is_prefix_only(line: &str) -> bool {
+    line[line_prefix(line).len()..].trim().is_empty()
+}
+
+/************************************************************************************************/
+
+fn wrap_prefixed(prefix: &str, text: &str, options: &WrapOptions) -> StringLines {
+    let prefix = if prefix.is_empty() || prefix.ends_with(char::is_whitespace) {
+        String::from(prefix)
+    } else {
+        format!("{} ", prefix)
+    };
+    let options = WrapOptions {
+        width: options.width.saturating_sub(display_width(&prefix)),
+        ..options.clone()
+    };
+
+    wrap_paragraph(text, &options)
+        .iter()
+        .map(|line| format!("{}{}", prefix, line))
+        .collect()
+}
+
+/************************************************************************************************/
+
+fn indent(options: &WrapOptions, row: usize) -> &str {
+    if row == 0 {
+        &options.initial_indent
+    } else {
+        &options.subsequent_indent
+    }
+}
+
+/************************************************************************************************/
+
+fn available_width(options: &WrapOptions, row: usize) -> usize {
+    options
+        .width
+        .saturating_sub(display_width(indent(options, row)))
+        .max(1)
+}
+
+/************************************************************************************************/
+
+fn justify_row(words: &[String], options: &WrapOptions, row: usize, last: bool) -> String {
+    let available = available_width(options, row);
+    let content = words.join(" ");
+    let free = available.saturating_sub(display_width(&content));
+    let mut line = String::from(indent(options, row));
+
+    match options.justify {
+        Justify::Left => line.push_str(&content),
+        Justify::Right => {
+            line.push_str(&" ".repeat(free));
+            line.push_str(&content);
+        }
+        Justify::Center => {
+            line.push_str(&" ".repeat(free / 2));
+            line.push_str(&content);
+        }
+        Justify::Full => {
+            if last || words.len() < 2 {
+                line.push_str(&content);
+            } else {
+                let gaps = words.len() - 1;
+                for (index, word) in words.iter().enumerate() {
+                    if index > 0 {
+                        let extra = free / gaps + if index <= free % gaps { 1 } else { 0 };
+                        line.push_str(&" ".repeat(1 + extra));
+                    }
+                    line.push_str(word);
+                }
+            }
+        }
+    }
+
+    line
+}
+
+/************************************************************************************************/
+/************************************************************************************************/
+/************************************************************************************************/